@@ -0,0 +1,280 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::error::Error;
+
+use crate::{TCS_SERVICE_VPC, TcsFilter, TcsTag, TcsWaiter, TencentCloudApi};
+
+// 弹性公网 IP
+// [查询弹性公网IP列表](https://cloud.tencent.com/document/api/215/16702)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsAddress {
+    #[serde(rename = "AddressId")]
+    pub address_id: String,
+    #[serde(rename = "AddressName")]
+    pub address_name: Option<String>,
+    // CREATING / BINDING / BIND / UNBINDING / UNBIND / OFFLINING / BIND_ENI
+    #[serde(rename = "AddressStatus")]
+    pub address_status: String,
+    #[serde(rename = "AddressIp")]
+    pub address_ip: String,
+    #[serde(rename = "InstanceId")]
+    pub instance_id: Option<String>,
+    #[serde(rename = "NetworkInterfaceId")]
+    pub network_interface_id: Option<String>,
+    #[serde(rename = "PrivateAddressIp")]
+    pub private_address_ip: Option<String>,
+    #[serde(rename = "AddressType")]
+    pub address_type: Option<String>,
+    #[serde(rename = "InternetChargeType")]
+    pub internet_charge_type: Option<String>,
+    #[serde(rename = "Bandwidth")]
+    pub bandwidth: Option<u64>,
+    #[serde(rename = "IsArrears")]
+    pub is_arrears: Option<bool>,
+    #[serde(rename = "IsBlocked")]
+    pub is_blocked: Option<bool>,
+    #[serde(rename = "CreatedTime")]
+    pub created_time: Option<String>,
+    #[serde(rename = "TagSet", default, deserialize_with = "crate::null_default")]
+    pub tag_set: Vec<TcsTag>,
+}
+
+impl TcsAddress {
+    // 是否已绑定到指定实例
+    pub fn is_bound_to(&self, instance_id: &str) -> bool {
+        self.address_status == "BIND" && self.instance_id.as_deref() == Some(instance_id)
+    }
+}
+
+// 申请弹性公网 IP 参数
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsAllocateAddressesRequest {
+    #[serde(rename = "AddressCount")]
+    pub address_count: u32,
+    #[serde(rename = "AddressName", skip_serializing_if = "Option::is_none")]
+    pub address_name: Option<String>,
+    // BANDWIDTH_POSTPAID_BY_HOUR / TRAFFIC_POSTPAID_BY_HOUR / BANDWIDTH_PACKAGE ...
+    #[serde(rename = "InternetChargeType", skip_serializing_if = "Option::is_none")]
+    pub internet_charge_type: Option<String>,
+    #[serde(rename = "InternetMaxBandwidthOut", skip_serializing_if = "Option::is_none")]
+    pub internet_max_bandwidth_out: Option<u32>,
+    #[serde(rename = "Tags", default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<TcsTag>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeAddresses {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "AddressSet")]
+    pub address_set: Vec<TcsAddress>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseAllocateAddresses {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "AddressSet")]
+    pub address_set: Vec<String>,
+    #[serde(rename = "TaskId")]
+    pub task_id: Option<String>,
+}
+
+// 异步任务接口响应
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseAddressTask {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TaskId")]
+    pub task_id: Option<String>,
+}
+
+impl TencentCloudApi {
+    // 查询弹性公网 IP 列表
+    pub fn tcs_describe_addresses(&mut self, tcs_region: &str, address_ids: &[String], filters: &[TcsFilter]) -> Result<Vec<TcsAddress>, Box<dyn Error>> {
+        info!("[######][查询弹性公网IP][@][tcs_describe_addresses()][tcs_region: {}][address_ids: {:?}][filters: {:?}]", tcs_region, address_ids, filters);
+
+        let limit = 100;
+        let mut address_set: Vec<TcsAddress> = vec![];
+        loop {
+            // AddressIds 与 Filters 不能同时指定
+            let mut payload = json!({
+                "Offset": address_set.len(),
+                "Limit": limit
+            });
+            if !address_ids.is_empty() {
+                payload["AddressIds"] = json!(address_ids);
+            } else if !filters.is_empty() {
+                payload["Filters"] = json!(filters);
+            }
+
+            let tcs_response_data: TcsResponseDescribeAddresses = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DescribeAddresses", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.address_set.len();
+            address_set.extend(tcs_response_data.address_set);
+
+            if page_count == 0 || address_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(address_set)
+    }
+
+    // 按名称查询弹性公网 IP(名称须唯一)
+    pub fn tcs_describe_address_by_name(&mut self, tcs_region: &str, address_name: &str) -> Result<TcsAddress, Box<dyn Error>> {
+        let filters = [TcsFilter::new("address-name", &[address_name])];
+        let mut address_set = self.tcs_describe_addresses(tcs_region, &[], &filters)?;
+
+        // 名称过滤为模糊匹配...
+        address_set.retain(|address| address.address_name.as_deref() == Some(address_name));
+        match address_set.len() {
+            1 => Ok(address_set.remove(0)),
+            0 => Result::Err(format!("弹性公网IP不存在({})!", address_name).into()),
+            _ => Result::Err(format!("弹性公网IP名称重复({})!", address_name).into()),
+        }
+    }
+
+    // 等待弹性公网 IP 状态
+    pub fn tcs_wait_address_state(&mut self, tcs_region: &str, address_id: &str, address_status: &str, waiter: &TcsWaiter) -> Result<TcsAddress, Box<dyn Error>> {
+        info!("[######][等待弹性公网IP状态][@][tcs_wait_address_state()][tcs_region: {}][address_id: {}][address_status: {}]", tcs_region, address_id, address_status);
+
+        let tcs_target = format!("{}:{}", address_id, address_status);
+        let address_ids = [address_id.to_string()];
+        waiter.wait(tcs_target.as_str(), || {
+            let address_set = self.tcs_describe_addresses(tcs_region, &address_ids, &[])?;
+            Ok(address_set.into_iter().find(|address| address.address_status == address_status))
+        })
+    }
+
+    // 申请弹性公网 IP
+    pub fn tcs_allocate_addresses(&mut self, tcs_region: &str, request: &TcsAllocateAddressesRequest) -> Result<Vec<String>, Box<dyn Error>> {
+        info!("[######][申请弹性公网IP][@][tcs_allocate_addresses()][tcs_region: {}][request: {:?}]", tcs_region, request);
+
+        let payload = serde_json::to_value(request)?;
+        let tcs_response_data: TcsResponseAllocateAddresses = self.tcs_request_service_action(&TCS_SERVICE_VPC, "AllocateAddresses", tcs_region, &payload)?;
+        Ok(tcs_response_data.address_set)
+    }
+
+    // 绑定弹性公网 IP(实例原有的普通公网 IP 将被释放)
+    pub fn tcs_associate_address(&mut self, tcs_region: &str, address_id: &str, instance_id: &str) -> Result<Option<String>, Box<dyn Error>> {
+        info!("[######][绑定弹性公网IP][@][tcs_associate_address()][tcs_region: {}][address_id: {}][instance_id: {}]", tcs_region, address_id, instance_id);
+
+        let payload = json!({
+            "AddressId": address_id,
+            "InstanceId": instance_id
+        });
+
+        let tcs_response_data: TcsResponseAddressTask = self.tcs_request_service_action(&TCS_SERVICE_VPC, "AssociateAddress", tcs_region, &payload)?;
+        Ok(tcs_response_data.task_id)
+    }
+
+    // 解绑弹性公网 IP
+    pub fn tcs_disassociate_address(&mut self, tcs_region: &str, address_id: &str, reallocate_normal_public_ip: bool) -> Result<Option<String>, Box<dyn Error>> {
+        info!(
+            "[######][解绑弹性公网IP][@][tcs_disassociate_address()][tcs_region: {}][address_id: {}][reallocate_normal_public_ip: {}]",
+            tcs_region, address_id, reallocate_normal_public_ip
+        );
+
+        let payload = json!({
+            "AddressId": address_id,
+            "ReallocateNormalPublicIp": reallocate_normal_public_ip
+        });
+
+        let tcs_response_data: TcsResponseAddressTask = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DisassociateAddress", tcs_region, &payload)?;
+        Ok(tcs_response_data.task_id)
+    }
+
+    // 释放弹性公网 IP(须先解绑)
+    pub fn tcs_release_addresses(&mut self, tcs_region: &str, address_ids: &[String]) -> Result<Option<String>, Box<dyn Error>> {
+        info!("[######][释放弹性公网IP][@][tcs_release_addresses()][tcs_region: {}][address_ids: {:?}]", tcs_region, address_ids);
+
+        let tcs_response_data: TcsResponseAddressTask = self.tcs_request_service_action(&TCS_SERVICE_VPC, "ReleaseAddresses", tcs_region, &json!({ "AddressIds": address_ids }))?;
+        Ok(tcs_response_data.task_id)
+    }
+
+    // 调整弹性公网 IP 带宽上限
+    pub fn tcs_modify_addresses_bandwidth(&mut self, tcs_region: &str, address_ids: &[String], internet_max_bandwidth_out: u32) -> Result<Option<String>, Box<dyn Error>> {
+        info!(
+            "[######][调整弹性公网IP带宽][@][tcs_modify_addresses_bandwidth()][tcs_region: {}][address_ids: {:?}][internet_max_bandwidth_out: {}]",
+            tcs_region, address_ids, internet_max_bandwidth_out
+        );
+
+        let payload = json!({
+            "AddressIds": address_ids,
+            "InternetMaxBandwidthOut": internet_max_bandwidth_out
+        });
+
+        let tcs_response_data: TcsResponseAddressTask = self.tcs_request_service_action(&TCS_SERVICE_VPC, "ModifyAddressesBandwidth", tcs_region, &payload)?;
+        Ok(tcs_response_data.task_id)
+    }
+
+    // 将指定名称的弹性公网 IP 迁移到新实例(例如竞价实例替换后), 已绑定到新实例时不做处理
+    pub fn tcs_move_address(&mut self, tcs_region: &str, address_name: &str, instance_id: &str, waiter: &TcsWaiter) -> Result<TcsAddress, Box<dyn Error>> {
+        info!("[######][迁移弹性公网IP][@][tcs_move_address()][tcs_region: {}][address_name: {}][instance_id: {}]", tcs_region, address_name, instance_id);
+
+        let address = self.tcs_describe_address_by_name(tcs_region, address_name)?;
+        if address.is_bound_to(instance_id) {
+            info!("[弹性公网IP已绑定][address_id: {}][address_ip: {}]", address.address_id, address.address_ip);
+            return Ok(address);
+        }
+
+        // 等待进行中的绑定/解绑操作结束...
+        let address_id = address.address_id.clone();
+        let address_id = address_id.as_str();
+        let address = match address.address_status.as_str() {
+            "BINDING" => self.tcs_wait_address_state(tcs_region, address_id, "BIND", waiter)?,
+            "UNBINDING" | "CREATING" => self.tcs_wait_address_state(tcs_region, address_id, "UNBIND", waiter)?,
+            _ => address,
+        };
+        if address.is_bound_to(instance_id) {
+            return Ok(address);
+        }
+
+        // 从旧实例解绑(旧实例可能已被回收)
+        if address.address_status == "BIND" {
+            info!("[解绑旧实例][address_id: {}][instance_id: {:?}]", address_id, address.instance_id);
+            self.tcs_disassociate_address(tcs_region, address_id, false)?;
+            self.tcs_wait_address_state(tcs_region, address_id, "UNBIND", waiter)?;
+        }
+
+        self.tcs_associate_address(tcs_region, address_id, instance_id)?;
+        let address = self.tcs_wait_address_state(tcs_region, address_id, "BIND", waiter)?;
+        if !address.is_bound_to(instance_id) {
+            return Result::Err(format!("弹性公网IP绑定失败({})!", address_id).into());
+        }
+
+        Ok(address)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::fixture;
+
+    #[test]
+    fn allocate_payload_and_binding() {
+        let request = TcsAllocateAddressesRequest {
+            address_count: 1,
+            address_name: Some("web-eip".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_value(&request).unwrap(), json!({ "AddressCount": 1, "AddressName": "web-eip" }));
+
+        let address: TcsAddress = fixture(json!({
+            "AddressId": "eip-1",
+            "AddressName": "web-eip",
+            "AddressStatus": "BIND",
+            "AddressIp": "1.2.3.4",
+            "InstanceId": "ins-1"
+        }));
+        assert!(address.is_bound_to("ins-1"));
+        assert!(!address.is_bound_to("ins-2"));
+    }
+}
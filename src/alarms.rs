@@ -0,0 +1,314 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::error::Error;
+
+use crate::{TCS_SERVICE_MONITOR, TcsResponseEmpty, TencentCloudApi};
+
+// 云服务器告警策略的命名空间
+pub const TCS_ALARM_NAMESPACE_CVM: &str = "cvm_device";
+
+// 告警触发条件
+// [告警策略相关数据结构](https://cloud.tencent.com/document/api/248/30354)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TcsAlarmPolicyRule {
+    #[serde(rename = "MetricName")]
+    pub metric_name: String,
+    // 统计周期(秒)
+    #[serde(rename = "Period", skip_serializing_if = "Option::is_none")]
+    pub period: Option<u64>,
+    // gt / ge / lt / le / eq / ne ...
+    #[serde(rename = "Operator", skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+    #[serde(rename = "Value", skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    // 持续周期数
+    #[serde(rename = "ContinuePeriod", skip_serializing_if = "Option::is_none")]
+    pub continue_period: Option<u64>,
+    // 告警间隔(秒)
+    #[serde(rename = "NoticeFrequency", skip_serializing_if = "Option::is_none")]
+    pub notice_frequency: Option<u64>,
+    // 告警频率是否指数增长, 0 / 1
+    #[serde(rename = "IsPowerNotice", skip_serializing_if = "Option::is_none")]
+    pub is_power_notice: Option<u32>,
+}
+
+impl TcsAlarmPolicyRule {
+    // 指标告警: 连续 continue_period 个周期满足条件时告警
+    pub fn metric(metric_name: &str, operator: &str, value: f64, period: u64, continue_period: u64) -> Self {
+        TcsAlarmPolicyRule {
+            metric_name: metric_name.to_string(),
+            period: Some(period),
+            operator: Some(operator.to_string()),
+            value: Some(value.to_string()),
+            continue_period: Some(continue_period),
+            notice_frequency: Some(3600),
+            is_power_notice: Some(0),
+        }
+    }
+
+    // 事件告警, 例如 ping_unreach / guest_reboot
+    pub fn event(metric_name: &str) -> Self {
+        TcsAlarmPolicyRule {
+            metric_name: metric_name.to_string(),
+            period: None,
+            operator: None,
+            value: None,
+            continue_period: None,
+            notice_frequency: Some(0),
+            is_power_notice: Some(0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TcsAlarmPolicyCondition {
+    // 0: 任意条件满足即告警, 1: 全部条件满足才告警
+    #[serde(rename = "IsUnionRule", default)]
+    pub is_union_rule: u32,
+    #[serde(rename = "Rules", default, deserialize_with = "crate::null_default")]
+    pub rules: Vec<TcsAlarmPolicyRule>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TcsAlarmPolicyEventCondition {
+    #[serde(rename = "Rules", default, deserialize_with = "crate::null_default")]
+    pub rules: Vec<TcsAlarmPolicyRule>,
+}
+
+// 告警策略
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsAlarmPolicy {
+    #[serde(rename = "PolicyId", default, skip_serializing)]
+    pub policy_id: String,
+    #[serde(rename = "PolicyName")]
+    pub policy_name: String,
+    #[serde(rename = "Remark", default)]
+    pub remark: String,
+    #[serde(rename = "MonitorType")]
+    pub monitor_type: String,
+    #[serde(rename = "Namespace")]
+    pub namespace: String,
+    #[serde(rename = "ProjectId", default)]
+    pub project_id: i64,
+    // 1: 启用, 0: 停用
+    #[serde(rename = "Enable", default)]
+    pub enable: u32,
+    #[serde(rename = "Condition")]
+    pub condition: Option<TcsAlarmPolicyCondition>,
+    #[serde(rename = "EventCondition")]
+    pub event_condition: Option<TcsAlarmPolicyEventCondition>,
+    // 通知模板ID
+    #[serde(rename = "NoticeIds", default, deserialize_with = "crate::null_default")]
+    pub notice_ids: Vec<String>,
+    // 绑定的对象数量(仅查询返回)
+    #[serde(rename = "UseSum", default, skip_serializing)]
+    pub use_sum: Option<u64>,
+}
+
+impl TcsAlarmPolicy {
+    pub fn new(policy_name: &str, namespace: &str) -> Self {
+        TcsAlarmPolicy {
+            policy_id: String::new(),
+            policy_name: policy_name.to_string(),
+            remark: String::new(),
+            monitor_type: "MT_QCE".to_string(),
+            namespace: namespace.to_string(),
+            project_id: 0,
+            enable: 1,
+            condition: None,
+            event_condition: None,
+            notice_ids: vec![],
+            use_sum: None,
+        }
+    }
+
+    // 云服务器标准告警: CPU 利用率连续 5 分钟 > 90%, 实例 ping 不可达
+    pub fn standard_cvm(policy_name: &str, notice_ids: &[String]) -> Self {
+        let mut policy = TcsAlarmPolicy::new(policy_name, TCS_ALARM_NAMESPACE_CVM);
+        policy.remark = "tcs-client standard alarms".to_string();
+        policy.condition = Some(TcsAlarmPolicyCondition {
+            is_union_rule: 0,
+            rules: vec![TcsAlarmPolicyRule::metric("CpuUsage", "gt", 90.0, 60, 5)],
+        });
+        policy.event_condition = Some(TcsAlarmPolicyEventCondition {
+            rules: vec![TcsAlarmPolicyRule::event("ping_unreach")],
+        });
+        policy.notice_ids = notice_ids.to_vec();
+        policy
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateAlarmPolicy {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "PolicyId")]
+    pub policy_id: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeAlarmPolicies {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "Policies")]
+    pub policies: Vec<TcsAlarmPolicy>,
+}
+
+impl TencentCloudApi {
+    // 创建告警策略
+    pub fn tcs_create_alarm_policy(&mut self, tcs_region: &str, policy: &TcsAlarmPolicy) -> Result<String, Box<dyn Error>> {
+        info!("[######][创建告警策略][@][tcs_create_alarm_policy()][tcs_region: {}][policy: {:?}]", tcs_region, policy);
+
+        let mut payload = serde_json::to_value(policy)?;
+        payload["Module"] = json!("monitor");
+
+        let tcs_response_data: TcsResponseCreateAlarmPolicy = self.tcs_request_service_action(&TCS_SERVICE_MONITOR, "CreateAlarmPolicy", tcs_region, &payload)?;
+        Ok(tcs_response_data.policy_id)
+    }
+
+    // 查询告警策略(policy_name 为模糊匹配)
+    pub fn tcs_describe_alarm_policies(&mut self, tcs_region: &str, policy_ids: &[String], policy_name: Option<&str>) -> Result<Vec<TcsAlarmPolicy>, Box<dyn Error>> {
+        info!("[######][查询告警策略][@][tcs_describe_alarm_policies()][tcs_region: {}][policy_ids: {:?}][policy_name: {:?}]", tcs_region, policy_ids, policy_name);
+
+        let page_size = 100;
+        let mut policies: Vec<TcsAlarmPolicy> = vec![];
+        loop {
+            // 页码从 1 开始
+            let mut payload = json!({
+                "Module": "monitor",
+                "PageNumber": policies.len() / page_size + 1,
+                "PageSize": page_size
+            });
+            if !policy_ids.is_empty() {
+                payload["PolicyIds"] = json!(policy_ids);
+            }
+            if let Some(policy_name) = policy_name {
+                payload["PolicyName"] = json!(policy_name);
+            }
+
+            let tcs_response_data: TcsResponseDescribeAlarmPolicies = self.tcs_request_service_action(&TCS_SERVICE_MONITOR, "DescribeAlarmPolicies", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.policies.len();
+            policies.extend(tcs_response_data.policies);
+
+            if page_count < page_size || policies.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(policies)
+    }
+
+    // 修改告警策略触发条件
+    pub fn tcs_modify_alarm_policy_condition(&mut self, tcs_region: &str, policy_id: &str, condition: &TcsAlarmPolicyCondition, event_condition: &TcsAlarmPolicyEventCondition) -> Result<(), Box<dyn Error>> {
+        info!(
+            "[######][修改告警策略条件][@][tcs_modify_alarm_policy_condition()][tcs_region: {}][policy_id: {}][condition: {:?}][event_condition: {:?}]",
+            tcs_region, policy_id, condition, event_condition
+        );
+
+        let payload = json!({
+            "Module": "monitor",
+            "PolicyId": policy_id,
+            "Condition": condition,
+            "EventCondition": event_condition
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_MONITOR, "ModifyAlarmPolicyCondition", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 修改告警策略名称(NAME)或备注(REMARK)
+    pub fn tcs_modify_alarm_policy_info(&mut self, tcs_region: &str, policy_id: &str, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+        info!("[######][修改告警策略信息][@][tcs_modify_alarm_policy_info()][tcs_region: {}][policy_id: {}][key: {}][value: {}]", tcs_region, policy_id, key, value);
+
+        let payload = json!({
+            "Module": "monitor",
+            "PolicyId": policy_id,
+            "Key": key,
+            "Value": value
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_MONITOR, "ModifyAlarmPolicyInfo", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 告警策略绑定实例
+    pub fn tcs_bind_alarm_policy_instances(&mut self, tcs_region: &str, policy_id: &str, instance_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!("[######][告警策略绑定实例][@][tcs_bind_alarm_policy_instances()][tcs_region: {}][policy_id: {}][instance_ids: {:?}]", tcs_region, policy_id, instance_ids);
+
+        // 维度为 JSON 字符串...
+        let dimensions: Vec<serde_json::Value> = instance_ids
+            .iter()
+            .map(|instance_id| {
+                json!({
+                    "Region": tcs_region,
+                    "Dimensions": json!({ "unInstanceId": instance_id }).to_string()
+                })
+            })
+            .collect();
+        let payload = json!({
+            "Module": "monitor",
+            "GroupId": 0,
+            "PolicyId": policy_id,
+            "Dimensions": dimensions
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_MONITOR, "BindingPolicyObject", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 删除告警策略
+    pub fn tcs_delete_alarm_policies(&mut self, tcs_region: &str, policy_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除告警策略][@][tcs_delete_alarm_policies()][tcs_region: {}][policy_ids: {:?}]", tcs_region, policy_ids);
+
+        let payload = json!({
+            "Module": "monitor",
+            "PolicyIds": policy_ids
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_MONITOR, "DeleteAlarmPolicy", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 按名称查找告警策略, 不存在时创建, 返回策略ID
+    pub fn tcs_ensure_alarm_policy(&mut self, tcs_region: &str, policy: &TcsAlarmPolicy) -> Result<String, Box<dyn Error>> {
+        let policies = self.tcs_describe_alarm_policies(tcs_region, &[], Some(policy.policy_name.as_str()))?;
+        if let Some(existing) = policies.iter().find(|existing| existing.policy_name == policy.policy_name && existing.namespace == policy.namespace) {
+            return Ok(existing.policy_id.clone());
+        }
+
+        self.tcs_create_alarm_policy(tcs_region, policy)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn standard_cvm_policy_payload() {
+        let policy = TcsAlarmPolicy::standard_cvm("fleet-web", &["notice-1".to_string()]);
+        let payload = serde_json::to_value(&policy).unwrap();
+
+        assert!(payload.get("PolicyId").is_none());
+        assert_eq!(payload["Namespace"], "cvm_device");
+        assert_eq!(
+            payload["Condition"]["Rules"][0],
+            json!({
+                "MetricName": "CpuUsage",
+                "Period": 60,
+                "Operator": "gt",
+                "Value": "90",
+                "ContinuePeriod": 5,
+                "NoticeFrequency": 3600,
+                "IsPowerNotice": 0
+            })
+        );
+        assert_eq!(payload["EventCondition"]["Rules"][0]["MetricName"], "ping_unreach");
+    }
+}
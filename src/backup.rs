@@ -0,0 +1,375 @@
+use chrono::{Datelike, Duration, FixedOffset, NaiveDateTime, Timelike, Utc};
+use log::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashSet;
+use std::error::Error;
+
+use crate::{TcsCbsDisk, TcsFilter, TcsSnapshot, TcsWaiter, TencentCloudApi};
+
+// 时钟(测试时注入固定时间)
+pub trait BackupClock {
+    fn now(&self) -> NaiveDateTime;
+}
+
+// 北京时间(与接口返回的创建时间一致), 不依赖主机时区
+pub struct SystemClock;
+
+impl BackupClock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Utc::now().with_timezone(&TCS_TIME_OFFSET).naive_local()
+    }
+}
+
+const TCS_TIME_OFFSET: FixedOffset = FixedOffset::east_opt(8 * 3600).unwrap();
+
+// 快照查询每个过滤条件最多 5 个取值
+const TCS_SNAPSHOT_FILTER_VALUES: usize = 5;
+
+// 备份时间表, crontab 格式: 分 时 日 月 周, 支持 * / 数字 / 列表 / 范围 / 步长
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct BackupSchedule {
+    expression: String,
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    days_of_week: Vec<bool>,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+// 解析单个字段, 返回 [min, max] 范围内的匹配表
+fn parse_schedule_field(field: &str, min: u32, max: u32) -> Result<Vec<bool>, String> {
+    let mut matched = vec![false; (max + 1) as usize];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| format!("步长错误({})!", part))?),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (start.parse::<u32>().map_err(|_| format!("范围错误({})!", part))?, end.parse::<u32>().map_err(|_| format!("范围错误({})!", part))?),
+                None => {
+                    let value = range.parse::<u32>().map_err(|_| format!("数值错误({})!", part))?;
+                    // 单个数值带步长时表示从该值开始
+                    if part.contains('/') { (value, max) } else { (value, value) }
+                }
+            },
+        };
+        if step == 0 || start < min || end > max || start > end {
+            return Result::Err(format!("取值超出范围({})!", part));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            matched[value as usize] = true;
+        }
+    }
+    Ok(matched)
+}
+
+impl BackupSchedule {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Result::Err(format!("时间表格式错误({})!", expression));
+        }
+
+        // 周日可写作 0 或 7
+        let mut days_of_week = parse_schedule_field(fields[4], 0, 7)?;
+        if days_of_week[7] {
+            days_of_week[0] = true;
+        }
+
+        Ok(BackupSchedule {
+            expression: expression.to_string(),
+            minutes: parse_schedule_field(fields[0], 0, 59)?,
+            hours: parse_schedule_field(fields[1], 0, 23)?,
+            days_of_month: parse_schedule_field(fields[2], 1, 31)?,
+            months: parse_schedule_field(fields[3], 1, 12)?,
+            days_of_week,
+            any_day_of_month: fields[2] == "*",
+            any_day_of_week: fields[4] == "*",
+        })
+    }
+
+    pub fn expression(&self) -> &str {
+        self.expression.as_str()
+    }
+
+    fn matches_day(&self, date: chrono::NaiveDate) -> bool {
+        if !self.months[date.month() as usize] {
+            return false;
+        }
+
+        let day_of_month = self.days_of_month[date.day() as usize];
+        let day_of_week = self.days_of_week[date.weekday().num_days_from_sunday() as usize];
+        // 与 crontab 一致: 日与周同时指定时满足其一即可
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (false, false) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+
+    // 不晚于 now 的最近一次触发时间(最多向前查找一年)
+    pub fn last_trigger(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        for days in 0..=366 {
+            let date = now.date() - Duration::days(days);
+            if !self.matches_day(date) {
+                continue;
+            }
+
+            let max_hour = if days == 0 { now.hour() } else { 23 };
+            for hour in (0..=max_hour).rev().filter(|hour| self.hours[*hour as usize]) {
+                let max_minute = if days == 0 && hour == now.hour() { now.minute() } else { 59 };
+                if let Some(minute) = (0..=max_minute).rev().find(|minute| self.minutes[*minute as usize]) {
+                    return date.and_hms_opt(hour, minute, 0);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl TryFrom<String> for BackupSchedule {
+    type Error = String;
+
+    fn try_from(expression: String) -> Result<Self, Self::Error> {
+        BackupSchedule::parse(expression.as_str())
+    }
+}
+
+impl From<BackupSchedule> for String {
+    fn from(schedule: BackupSchedule) -> Self {
+        schedule.expression
+    }
+}
+
+// 保留策略: 最近 daily 天每天保留一份, 最近 weekly 周每周保留一份
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BackupRetention {
+    pub daily: usize,
+    pub weekly: usize,
+}
+
+impl BackupRetention {
+    // 返回需要保留的快照下标(snapshot_times 按时间倒序)
+    pub fn keep(&self, snapshot_times: &[NaiveDateTime]) -> HashSet<usize> {
+        let mut kept = HashSet::new();
+
+        let mut days = HashSet::new();
+        let mut weeks = HashSet::new();
+        for (index, snapshot_time) in snapshot_times.iter().enumerate() {
+            if days.len() < self.daily && days.insert(snapshot_time.date()) {
+                kept.insert(index);
+            }
+            let week = snapshot_time.iso_week();
+            if weeks.len() < self.weekly && weeks.insert((week.year(), week.week())) {
+                kept.insert(index);
+            }
+        }
+        kept
+    }
+}
+
+// 备份计划
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BackupPlan {
+    // (云硬盘ID, 快照名称)
+    pub create: Vec<(String, String)>,
+    pub delete: Vec<String>,
+}
+
+// 快照备份策略: 按标签选择云硬盘
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BackupPolicy {
+    pub tcs_region: String,
+    pub tag_key: String,
+    pub tag_value: String,
+    // 快照名称前缀, 仅管理该前缀的快照
+    pub snapshot_prefix: String,
+    pub schedule: BackupSchedule,
+    pub retention: BackupRetention,
+}
+
+pub const TCS_SNAPSHOT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl BackupPolicy {
+    pub fn snapshot_name(&self, disk_id: &str, now: NaiveDateTime) -> String {
+        format!("{}-{}-{}", self.snapshot_prefix, disk_id, now.format("%Y%m%d%H%M"))
+    }
+
+    // 计算需要创建与删除的快照(不调用接口)
+    pub fn plan(&self, disks: &[TcsCbsDisk], snapshots: &[TcsSnapshot], now: NaiveDateTime) -> Result<BackupPlan, Box<dyn Error>> {
+        if self.retention.daily == 0 && self.retention.weekly == 0 {
+            return Result::Err("保留策略至少保留一份快照!".into());
+        }
+
+        let last_trigger = self.schedule.last_trigger(now);
+        let mut plan = BackupPlan::default();
+        for disk in disks {
+            let prefix = format!("{}-{}-", self.snapshot_prefix, disk.disk_id);
+            let mut disk_snapshots: Vec<(NaiveDateTime, &TcsSnapshot)> = vec![];
+            for snapshot in snapshots.iter().filter(|snapshot| snapshot.disk_id == disk.disk_id && snapshot.snapshot_name.starts_with(&prefix)) {
+                let create_time = NaiveDateTime::parse_from_str(snapshot.create_time.as_str(), TCS_SNAPSHOT_TIME_FORMAT)?;
+                disk_snapshots.push((create_time, snapshot));
+            }
+            disk_snapshots.sort_by_key(|(create_time, _)| std::cmp::Reverse(*create_time));
+
+            // 上次触发后没有快照(含创建中)则需要备份
+            let latest = disk_snapshots.iter().filter(|(_, snapshot)| snapshot.snapshot_state != "TORECYCLE").map(|(create_time, _)| *create_time).next();
+            let due = match (last_trigger, latest) {
+                (Some(last_trigger), Some(latest)) => latest < last_trigger,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if due {
+                plan.create.push((disk.disk_id.clone(), self.snapshot_name(disk.disk_id.as_str(), now)));
+            }
+
+            // 本次新建快照占用保留名额, 创建中的快照不删除
+            let normal_snapshots: Vec<&(NaiveDateTime, &TcsSnapshot)> = disk_snapshots.iter().filter(|(_, snapshot)| snapshot.snapshot_state == "NORMAL").collect();
+            let mut snapshot_times: Vec<NaiveDateTime> = if due { vec![now] } else { vec![] };
+            snapshot_times.extend(normal_snapshots.iter().map(|(create_time, _)| *create_time));
+
+            let kept = self.retention.keep(&snapshot_times);
+            let offset = if due { 1 } else { 0 };
+            for (index, (_, snapshot)) in normal_snapshots.iter().enumerate() {
+                if !kept.contains(&(index + offset)) {
+                    plan.delete.push(snapshot.snapshot_id.clone());
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    // 查询云硬盘与快照并生成备份计划
+    pub fn describe_plan(&self, api: &mut TencentCloudApi, clock: &dyn BackupClock) -> Result<BackupPlan, Box<dyn Error>> {
+        let tcs_region = self.tcs_region.as_str();
+        let disk_filters = [TcsFilter::new(format!("tag:{}", self.tag_key).as_str(), &[self.tag_value.as_str()])];
+        let disks = api.tcs_describe_disks(tcs_region, &[], &disk_filters)?;
+        if disks.is_empty() {
+            return Ok(BackupPlan::default());
+        }
+
+        let disk_ids: Vec<&str> = disks.iter().map(|disk| disk.disk_id.as_str()).collect();
+        let mut snapshots = vec![];
+        for disk_ids in disk_ids.chunks(TCS_SNAPSHOT_FILTER_VALUES) {
+            snapshots.extend(api.tcs_describe_snapshots(tcs_region, &[], &[TcsFilter::new("disk-id", disk_ids)])?);
+        }
+
+        self.plan(&disks, &snapshots, clock.now())
+    }
+
+    // 执行一次备份(可由 cron 重复调用: 已备份过的时间点不会重复创建)
+    // 新快照全部创建成功后才删除过期快照, 出错时保留全部旧快照
+    pub fn run(&self, api: &mut TencentCloudApi, clock: &dyn BackupClock, waiter: &TcsWaiter) -> Result<BackupPlan, Box<dyn Error>> {
+        info!("[######][快照备份][@][run()][tcs_region: {}][tag: {}={}][schedule: {}]", self.tcs_region, self.tag_key, self.tag_value, self.schedule.expression());
+
+        let plan = self.describe_plan(api, clock)?;
+        let tcs_region = self.tcs_region.as_str();
+        let mut snapshot_ids = vec![];
+        for (disk_id, snapshot_name) in &plan.create {
+            snapshot_ids.push(api.tcs_create_snapshot(tcs_region, disk_id, snapshot_name, None)?);
+        }
+        for snapshot_id in &snapshot_ids {
+            api.tcs_wait_snapshot_state(tcs_region, snapshot_id.as_str(), "NORMAL", waiter)?;
+        }
+        if !plan.delete.is_empty() {
+            api.tcs_delete_snapshots(tcs_region, &plan.delete)?;
+        }
+
+        info!("[快照备份完成][create: {}][delete: {}]", plan.create.len(), plan.delete.len());
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::fixture;
+
+    struct FixedClock(NaiveDateTime);
+
+    impl BackupClock for FixedClock {
+        fn now(&self) -> NaiveDateTime {
+            self.0
+        }
+    }
+
+    fn time(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, TCS_SNAPSHOT_TIME_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn schedule_last_trigger() {
+        let schedule = BackupSchedule::parse("30 2 * * *").unwrap();
+        assert_eq!(schedule.last_trigger(time("2026-03-10 02:29:59")), Some(time("2026-03-09 02:30:00")));
+        assert_eq!(schedule.last_trigger(time("2026-03-10 02:30:00")), Some(time("2026-03-10 02:30:00")));
+
+        // 每周日 03:00
+        let schedule = BackupSchedule::parse("0 3 * * 7").unwrap();
+        assert_eq!(schedule.last_trigger(time("2026-03-10 12:00:00")), Some(time("2026-03-08 03:00:00")));
+
+        let schedule = BackupSchedule::parse("*/15 */6 * * 1-5").unwrap();
+        assert_eq!(schedule.last_trigger(time("2026-03-10 13:20:00")), Some(time("2026-03-10 12:45:00")));
+
+        assert!(BackupSchedule::parse("0 24 * * *").is_err());
+        assert!(BackupSchedule::parse("0 2 * *").is_err());
+    }
+
+    #[test]
+    fn plan_backup_with_retention() {
+        let disk: TcsCbsDisk = fixture(serde_json::json!({
+            "DiskId": "disk-1",
+            "DiskName": "data",
+            "DiskUsage": "DATA_DISK",
+            "DiskType": "CLOUD_SSD",
+            "DiskSize": 100,
+            "DiskState": "ATTACHED",
+            "DiskChargeType": "POSTPAID_BY_HOUR",
+            "Placement": { "Zone": "ap-guangzhou-3" },
+            "Portable": true,
+            "Attached": true
+        }));
+
+        // 每天 02:00 备份, 已有 2026-03-01 ~ 2026-03-09 每天一份
+        let snapshots: Vec<TcsSnapshot> = (1..=9)
+            .map(|day| {
+                fixture(serde_json::json!({
+                    "SnapshotId": format!("snap-{}", day),
+                    "SnapshotName": format!("backup-disk-1-202603{:02}0200", day),
+                    "SnapshotState": "NORMAL",
+                    "DiskId": "disk-1",
+                    "DiskSize": 100,
+                    "CreateTime": format!("2026-03-{:02} 02:00:05", day)
+                }))
+            })
+            .collect();
+
+        let policy = BackupPolicy {
+            tcs_region: "ap-guangzhou".to_string(),
+            tag_key: "backup".to_string(),
+            tag_value: "daily".to_string(),
+            snapshot_prefix: "backup".to_string(),
+            schedule: BackupSchedule::parse("0 2 * * *").unwrap(),
+            retention: BackupRetention { daily: 3, weekly: 2 },
+        };
+
+        let clock = FixedClock(time("2026-03-10 02:10:00"));
+        let plan = policy.plan(std::slice::from_ref(&disk), &snapshots, clock.now()).unwrap();
+        assert_eq!(plan.create, vec![("disk-1".to_string(), "backup-disk-1-202603100210".to_string())]);
+        // 按天保留 03-10(新建) / 03-09 / 03-08, 按周保留本周(新建)与上周最新的 03-08
+        let mut deleted = plan.delete.clone();
+        deleted.sort();
+        assert_eq!(deleted, vec!["snap-1", "snap-2", "snap-3", "snap-4", "snap-5", "snap-6", "snap-7"]);
+
+        // 同一触发周期内重复执行不再创建
+        let clock = FixedClock(time("2026-03-09 23:00:00"));
+        let plan = policy.plan(std::slice::from_ref(&disk), &snapshots, clock.now()).unwrap();
+        assert!(plan.create.is_empty());
+    }
+}
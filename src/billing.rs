@@ -0,0 +1,513 @@
+use chrono::{Datelike, NaiveDate};
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use crate::{TCS_SERVICE_BILLING, TcsData, TencentCloudApi};
+
+// 账单接口不区分地域
+const TCS_BILLING_REGION: &str = "";
+
+// 账单金额均为字符串, 解析失败按 0 计
+fn parse_cost(cost: &str) -> f64 {
+    cost.trim().parse().unwrap_or(0.0)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TcsBillTag {
+    #[serde(rename = "TagKey")]
+    pub tag_key: String,
+    #[serde(rename = "TagValue")]
+    pub tag_value: String,
+}
+
+// 账单组件明细
+// [计费相关数据结构](https://cloud.tencent.com/document/api/555/19183)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsBillDetailComponent {
+    #[serde(rename = "ComponentCodeName", default)]
+    pub component_code_name: String,
+    #[serde(rename = "ItemCodeName", default)]
+    pub item_code_name: String,
+    #[serde(rename = "SinglePrice", default)]
+    pub single_price: String,
+    #[serde(rename = "PriceUnit", default)]
+    pub price_unit: String,
+    #[serde(rename = "UsedAmount", default)]
+    pub used_amount: String,
+    #[serde(rename = "UsedAmountUnit", default)]
+    pub used_amount_unit: String,
+    #[serde(rename = "Cost", default)]
+    pub cost: String,
+    #[serde(rename = "Discount", default)]
+    pub discount: String,
+    // 折后实际费用
+    #[serde(rename = "RealCost", default)]
+    pub real_cost: String,
+}
+
+// 账单明细
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsBillDetail {
+    #[serde(rename = "BusinessCodeName", default)]
+    pub business_code_name: String,
+    #[serde(rename = "ProductCodeName", default)]
+    pub product_code_name: String,
+    #[serde(rename = "PayModeName", default)]
+    pub pay_mode_name: String,
+    #[serde(rename = "RegionName", default)]
+    pub region_name: String,
+    #[serde(rename = "ZoneName", default)]
+    pub zone_name: String,
+    #[serde(rename = "ResourceId", default)]
+    pub resource_id: String,
+    #[serde(rename = "ResourceName", default)]
+    pub resource_name: String,
+    #[serde(rename = "ActionTypeName", default)]
+    pub action_type_name: String,
+    #[serde(rename = "BillId", default)]
+    pub bill_id: String,
+    #[serde(rename = "PayTime", default)]
+    pub pay_time: String,
+    #[serde(rename = "FeeBeginTime", default)]
+    pub fee_begin_time: String,
+    #[serde(rename = "FeeEndTime", default)]
+    pub fee_end_time: String,
+    #[serde(rename = "ComponentSet", default, deserialize_with = "crate::null_default")]
+    pub component_set: Vec<TcsBillDetailComponent>,
+    #[serde(rename = "Tags", default, deserialize_with = "crate::null_default")]
+    pub tags: Vec<TcsBillTag>,
+    #[serde(rename = "BusinessCode", default)]
+    pub business_code: String,
+    #[serde(rename = "ProductCode", default)]
+    pub product_code: String,
+    #[serde(rename = "RegionId", default)]
+    pub region_id: String,
+}
+
+impl TcsBillDetail {
+    // 折后实际费用合计(元)
+    pub fn real_cost(&self) -> f64 {
+        self.component_set.iter().map(|component| parse_cost(&component.real_cost)).sum()
+    }
+
+    pub fn tag_value(&self, tag_key: &str) -> Option<&str> {
+        self.tags.iter().find(|tag| tag.tag_key == tag_key).map(|tag| tag.tag_value.as_str())
+    }
+}
+
+// 按资源汇总的账单
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsBillResourceSummary {
+    #[serde(rename = "BusinessCodeName", default)]
+    pub business_code_name: String,
+    #[serde(rename = "ProductCodeName", default)]
+    pub product_code_name: String,
+    #[serde(rename = "PayModeName", default)]
+    pub pay_mode_name: String,
+    #[serde(rename = "RegionName", default)]
+    pub region_name: String,
+    #[serde(rename = "ResourceId", default)]
+    pub resource_id: String,
+    #[serde(rename = "ResourceName", default)]
+    pub resource_name: String,
+    #[serde(rename = "ConfigDesc", default)]
+    pub config_desc: String,
+    #[serde(rename = "FeeBeginTime", default)]
+    pub fee_begin_time: String,
+    #[serde(rename = "FeeEndTime", default)]
+    pub fee_end_time: String,
+    #[serde(rename = "TotalCost", default)]
+    pub total_cost: String,
+    #[serde(rename = "RealTotalCost", default)]
+    pub real_total_cost: String,
+    #[serde(rename = "CashPayAmount", default)]
+    pub cash_pay_amount: String,
+    #[serde(rename = "VoucherPayAmount", default)]
+    pub voucher_pay_amount: String,
+    #[serde(rename = "Tags", default, deserialize_with = "crate::null_default")]
+    pub tags: Vec<TcsBillTag>,
+    #[serde(rename = "BusinessCode", default)]
+    pub business_code: String,
+    #[serde(rename = "RegionId", default)]
+    pub region_id: String,
+    #[serde(rename = "InstanceType", default)]
+    pub instance_type: String,
+}
+
+// 账户余额(单位: 分)
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsAccountBalance {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "Balance")]
+    pub balance: i64,
+    #[serde(rename = "Uin", default)]
+    pub uin: u64,
+    #[serde(rename = "RealBalance", default)]
+    pub real_balance: f64,
+    #[serde(rename = "CashAccountBalance", default)]
+    pub cash_account_balance: f64,
+    #[serde(rename = "PresentAccountBalance", default)]
+    pub present_account_balance: f64,
+    #[serde(rename = "FreezeAmount", default)]
+    pub freeze_amount: f64,
+    #[serde(rename = "OweAmount", default)]
+    pub owe_amount: f64,
+    #[serde(rename = "IsAllowArrears", default)]
+    pub is_allow_arrears: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsDosageDetailPoint {
+    #[serde(rename = "Time")]
+    pub time: String,
+    #[serde(rename = "Value")]
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsDosageDetailSet {
+    #[serde(rename = "Domain", default)]
+    pub domain: Option<String>,
+    #[serde(rename = "InstanceID", default)]
+    pub instance_id: Option<String>,
+    #[serde(rename = "DetailPoints", default, deserialize_with = "crate::null_default")]
+    pub detail_points: Vec<TcsDosageDetailPoint>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeDosageDetailByDate {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "Unit", default)]
+    pub unit: Option<String>,
+    #[serde(rename = "DetailSets", default, deserialize_with = "crate::null_default")]
+    pub detail_sets: Vec<TcsDosageDetailSet>,
+    #[serde(rename = "RetCode", default)]
+    pub ret_code: Option<i64>,
+    #[serde(rename = "RetMsg", default)]
+    pub ret_msg: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeBillDetail {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "DetailSet", default, deserialize_with = "crate::null_default")]
+    pub detail_set: Vec<TcsBillDetail>,
+    #[serde(rename = "Total", default)]
+    pub total: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeBillResourceSummary {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "ResourceSummarySet", default, deserialize_with = "crate::null_default")]
+    pub resource_summary_set: Vec<TcsBillResourceSummary>,
+    #[serde(rename = "Total", default)]
+    pub total: u64,
+}
+
+// 账单明细查询条件(起止日期均包含)
+#[derive(Debug, Clone)]
+pub struct TcsBillQuery {
+    pub begin_date: NaiveDate,
+    pub end_date: NaiveDate,
+    // 产品编码, 例如 p_cvm
+    pub product_code: Option<String>,
+    // 付费模式, 例如 postPay
+    pub pay_mode: Option<String>,
+}
+
+impl TcsBillQuery {
+    pub fn new(begin_date: NaiveDate, end_date: NaiveDate) -> Self {
+        TcsBillQuery {
+            begin_date,
+            end_date,
+            product_code: None,
+            pay_mode: None,
+        }
+    }
+
+    pub fn with_product_code(mut self, product_code: &str) -> Self {
+        self.product_code = Some(product_code.to_string());
+        self
+    }
+
+    pub fn with_pay_mode(mut self, pay_mode: &str) -> Self {
+        self.pay_mode = Some(pay_mode.to_string());
+        self
+    }
+}
+
+// 账单接口的起止时间必须在同一个月内, 按自然月拆分
+pub fn split_bill_months(begin_date: NaiveDate, end_date: NaiveDate) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut months = vec![];
+    let mut month_begin = begin_date;
+    while month_begin <= end_date {
+        let next_month = if month_begin.month() == 12 {
+            NaiveDate::from_ymd_opt(month_begin.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(month_begin.year(), month_begin.month() + 1, 1)
+        };
+        let Some(next_month) = next_month else { break };
+        let month_end = next_month.pred_opt().unwrap_or(next_month).min(end_date);
+
+        months.push((month_begin, month_end));
+        month_begin = next_month;
+    }
+    months
+}
+
+// 费用分组方式
+#[derive(Debug, Clone)]
+pub enum TcsCostGroupBy {
+    // 按资源名称前缀(前缀后需为 '-' 或名称结尾), 匹配最长的前缀, 未匹配的资源不计入
+    NamePrefix(Vec<String>),
+    // 按标签值, 未设置该标签的资源不计入
+    Tag(String),
+}
+
+// web 匹配 web / web-0, 不匹配 webhook-1
+fn name_has_prefix(name: &str, prefix: &str) -> bool {
+    name.strip_prefix(prefix).is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+}
+
+// 按分组汇总折后费用(元)
+pub fn group_bill_costs(details: &[TcsBillDetail], group_by: &TcsCostGroupBy) -> BTreeMap<String, f64> {
+    let mut costs: BTreeMap<String, f64> = BTreeMap::new();
+    for detail in details {
+        let group = match group_by {
+            TcsCostGroupBy::NamePrefix(prefixes) => prefixes.iter().filter(|prefix| name_has_prefix(detail.resource_name.as_str(), prefix)).max_by_key(|prefix| prefix.len()).cloned(),
+            TcsCostGroupBy::Tag(tag_key) => detail.tag_value(tag_key).map(|tag_value| tag_value.to_string()),
+        };
+        if let Some(group) = group {
+            *costs.entry(group).or_insert(0.0) += detail.real_cost();
+        }
+    }
+    costs
+}
+
+// 按 TcsData 分组汇总折后费用: 账单资源ID/名称与 instance_id/instance_name 完全一致时计入该配置的 tcs_title
+pub fn group_bill_costs_by_title(details: &[TcsBillDetail], tcs_data_list: &[TcsData]) -> BTreeMap<String, f64> {
+    let mut costs: BTreeMap<String, f64> = BTreeMap::new();
+    for detail in details {
+        let tcs_data = tcs_data_list
+            .iter()
+            .find(|tcs_data| !tcs_data.instance_id.is_empty() && tcs_data.instance_id == detail.resource_id)
+            .or_else(|| tcs_data_list.iter().find(|tcs_data| !tcs_data.instance_name.is_empty() && tcs_data.instance_name == detail.resource_name));
+        if let Some(tcs_data) = tcs_data {
+            *costs.entry(tcs_data.tcs_title.clone()).or_insert(0.0) += detail.real_cost();
+        }
+    }
+    costs
+}
+
+// 导出费用汇总 CSV
+pub fn render_cost_csv(costs: &BTreeMap<String, f64>) -> String {
+    let mut lines = vec!["group,real_cost".to_string()];
+    for (group, cost) in costs {
+        let group = if group.contains([',', '"', '\n']) { format!("\"{}\"", group.replace('"', "\"\"")) } else { group.clone() };
+        lines.push(format!("{},{:.2}", group, cost));
+    }
+    lines.join("\n") + "\n"
+}
+
+impl TencentCloudApi {
+    // 查询账单明细(跨月时按月拆分查询)
+    // [查询账单明细数据](https://cloud.tencent.com/document/api/555/19182)
+    pub fn tcs_describe_bill_detail(&mut self, query: &TcsBillQuery) -> Result<Vec<TcsBillDetail>, Box<dyn Error>> {
+        info!("[######][查询账单明细][@][tcs_describe_bill_detail()][query: {:?}]", query);
+
+        let limit = 100;
+        let mut details: Vec<TcsBillDetail> = vec![];
+        for (month_begin, month_end) in split_bill_months(query.begin_date, query.end_date) {
+            let mut offset = 0;
+            loop {
+                let mut payload = json!({
+                    "Offset": offset,
+                    "Limit": limit,
+                    "PeriodType": "byUsedTime",
+                    "BeginTime": format!("{} 00:00:00", month_begin.format("%Y-%m-%d")),
+                    "EndTime": format!("{} 23:59:59", month_end.format("%Y-%m-%d")),
+                    "NeedRecordNum": 1
+                });
+                if let Some(product_code) = &query.product_code {
+                    payload["ProductCode"] = json!(product_code);
+                }
+                if let Some(pay_mode) = &query.pay_mode {
+                    payload["PayMode"] = json!(pay_mode);
+                }
+
+                let tcs_response_data: TcsResponseDescribeBillDetail = self.tcs_request_service_action(&TCS_SERVICE_BILLING, "DescribeBillDetail", TCS_BILLING_REGION, &payload)?;
+
+                let page_count = tcs_response_data.detail_set.len();
+                details.extend(tcs_response_data.detail_set);
+                offset += page_count;
+
+                if page_count == 0 || offset as u64 >= tcs_response_data.total {
+                    break;
+                }
+            }
+        }
+
+        Ok(details)
+    }
+
+    // 查询资源汇总账单, month 格式为 2024-01
+    pub fn tcs_describe_bill_resource_summary(&mut self, month: &str, tag_key: Option<&str>) -> Result<Vec<TcsBillResourceSummary>, Box<dyn Error>> {
+        info!("[######][查询资源汇总账单][@][tcs_describe_bill_resource_summary()][month: {}][tag_key: {:?}]", month, tag_key);
+
+        let limit = 1000;
+        let mut summaries: Vec<TcsBillResourceSummary> = vec![];
+        loop {
+            let mut payload = json!({
+                "Offset": summaries.len(),
+                "Limit": limit,
+                "Month": month,
+                "PeriodType": "byUsedTime",
+                "NeedRecordNum": 1
+            });
+            if let Some(tag_key) = tag_key {
+                payload["TagKey"] = json!(tag_key);
+            }
+
+            let tcs_response_data: TcsResponseDescribeBillResourceSummary = self.tcs_request_service_action(&TCS_SERVICE_BILLING, "DescribeBillResourceSummary", TCS_BILLING_REGION, &payload)?;
+
+            let page_count = tcs_response_data.resource_summary_set.len();
+            summaries.extend(tcs_response_data.resource_summary_set);
+
+            if page_count == 0 || summaries.len() as u64 >= tcs_response_data.total {
+                break;
+            }
+        }
+
+        Ok(summaries)
+    }
+
+    // 查询账户余额
+    pub fn tcs_describe_account_balance(&mut self) -> Result<TcsAccountBalance, Box<dyn Error>> {
+        info!("[######][查询账户余额][@][tcs_describe_account_balance()]");
+
+        let tcs_response_data: TcsAccountBalance = self.tcs_request_service_action(&TCS_SERVICE_BILLING, "DescribeAccountBalance", TCS_BILLING_REGION, &json!({}))?;
+        Ok(tcs_response_data)
+    }
+
+    // 按日期查询产品用量明细(起止日期需在同一个月内)
+    pub fn tcs_describe_dosage_detail_by_date(&mut self, product_code: &str, begin_date: NaiveDate, end_date: NaiveDate, instance_id: Option<&str>) -> Result<TcsResponseDescribeDosageDetailByDate, Box<dyn Error>> {
+        info!(
+            "[######][查询用量明细][@][tcs_describe_dosage_detail_by_date()][product_code: {}][begin_date: {}][end_date: {}][instance_id: {:?}]",
+            product_code, begin_date, end_date, instance_id
+        );
+
+        let mut payload = json!({
+            "StartDate": begin_date.format("%Y-%m-%d").to_string(),
+            "EndDate": end_date.format("%Y-%m-%d").to_string(),
+            "ProductCode": product_code
+        });
+        if let Some(instance_id) = instance_id {
+            payload["InstanceID"] = json!(instance_id);
+        }
+
+        let tcs_response_data: TcsResponseDescribeDosageDetailByDate = self.tcs_request_service_action(&TCS_SERVICE_BILLING, "DescribeDosageDetailByDate", TCS_BILLING_REGION, &payload)?;
+        if let Some(ret_code) = tcs_response_data.ret_code
+            && ret_code != 0
+        {
+            return Result::Err(format!("查询用量明细失败({}: {})!", ret_code, tcs_response_data.ret_msg.clone().unwrap_or_default()).into());
+        }
+
+        Ok(tcs_response_data)
+    }
+
+    // 按分组汇总日期范围内的折后费用
+    pub fn tcs_group_costs(&mut self, query: &TcsBillQuery, group_by: &TcsCostGroupBy) -> Result<BTreeMap<String, f64>, Box<dyn Error>> {
+        let details = self.tcs_describe_bill_detail(query)?;
+        let costs = group_bill_costs(&details, group_by);
+
+        info!("[######][汇总费用][@][tcs_group_costs()][group_by: {:?}][costs: {:?}]", group_by, costs);
+        Ok(costs)
+    }
+
+    // 按 tcs_title 汇总竞价实例费用(仅统计计费类型为 SPOTPAID 的配置)
+    pub fn tcs_spot_costs_by_title(&mut self, tcs_data_list: &[TcsData], begin_date: NaiveDate, end_date: NaiveDate) -> Result<BTreeMap<String, f64>, Box<dyn Error>> {
+        let spot_data_list: Vec<TcsData> = tcs_data_list.iter().filter(|tcs_data| tcs_data.tcs_info.instance_charge_type == "SPOTPAID").cloned().collect();
+        if spot_data_list.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        // 竞价实例按量计费, 账单中与普通按量实例无法区分, 以实例配置区分
+        let query = TcsBillQuery::new(begin_date, end_date).with_product_code("p_cvm").with_pay_mode("postPay");
+        let details = self.tcs_describe_bill_detail(&query)?;
+        let costs = group_bill_costs_by_title(&details, &spot_data_list);
+
+        info!("[######][汇总竞价实例费用][@][tcs_spot_costs_by_title()][costs: {:?}]", costs);
+        Ok(costs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::{fixture, tcs_data};
+
+    fn detail(resource_name: &str, team: Option<&str>, real_costs: &[&str]) -> TcsBillDetail {
+        fixture(json!({
+            "ResourceId": format!("ins-{}", resource_name),
+            "ResourceName": resource_name,
+            "Tags": team.map(|team| vec![json!({ "TagKey": "team", "TagValue": team })]).unwrap_or_default(),
+            "ComponentSet": real_costs.iter().map(|real_cost| json!({ "RealCost": real_cost })).collect::<Vec<_>>()
+        }))
+    }
+
+    #[test]
+    fn split_months_and_group_costs() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(
+            split_bill_months(date(2023, 12, 20), date(2024, 2, 10)),
+            vec![(date(2023, 12, 20), date(2023, 12, 31)), (date(2024, 1, 1), date(2024, 1, 31)), (date(2024, 2, 1), date(2024, 2, 10))]
+        );
+
+        let details = vec![
+            detail("web-0", Some("infra"), &["1.20", "0.30"]),
+            detail("web-api-1", Some("infra"), &["2.00"]),
+            detail("batch-3", None, &["0.50"]),
+        ];
+
+        let costs = group_bill_costs(&details, &TcsCostGroupBy::NamePrefix(vec!["web".to_string(), "web-api".to_string()]));
+        assert_eq!(costs.len(), 2);
+        assert!((costs["web"] - 1.5).abs() < 1e-9);
+        assert!((costs["web-api"] - 2.0).abs() < 1e-9);
+
+        let costs = group_bill_costs(&details, &TcsCostGroupBy::Tag("team".to_string()));
+        assert_eq!(render_cost_csv(&costs), "group,real_cost\ninfra,3.50\n");
+    }
+
+    #[test]
+    fn group_costs_by_title() {
+        let details = vec![
+            detail("web-0", None, &["1.00"]),
+            detail("webhook-1", None, &["2.00"]),
+            detail("renamed", None, &["4.00"]),
+            detail("batch-3", None, &["8.00"]),
+        ];
+
+        let mut web = tcs_data("web", "web-0", "SPOTPAID");
+        web.instance_id = String::new();
+        let mut hook = tcs_data("hook", "webhook-1", "SPOTPAID");
+        hook.instance_id = "ins-renamed".to_string();
+
+        // 按实例ID或完整名称匹配, 不按前缀匹配
+        let costs = group_bill_costs_by_title(&details, &[web, hook]);
+        assert_eq!(costs.len(), 2);
+        assert!((costs["web"] - 1.0).abs() < 1e-9);
+        assert!((costs["hook"] - 6.0).abs() < 1e-9);
+
+        let costs = group_bill_costs(&details, &TcsCostGroupBy::NamePrefix(vec!["web".to_string()]));
+        assert!((costs["web"] - 1.0).abs() < 1e-9);
+    }
+}
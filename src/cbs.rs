@@ -0,0 +1,525 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::error::Error;
+
+use crate::{TCS_SERVICE_CBS, TcsFilter, TcsPlacement, TcsResponseEmpty, TcsTag, TcsWaiter, TencentCloudApi};
+
+// 云硬盘
+// [查询云硬盘列表](https://cloud.tencent.com/document/api/362/16315)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsCbsDisk {
+    #[serde(rename = "DiskId")]
+    pub disk_id: String,
+    #[serde(rename = "DiskName")]
+    pub disk_name: String,
+    // SYSTEM_DISK / DATA_DISK
+    #[serde(rename = "DiskUsage")]
+    pub disk_usage: String,
+    #[serde(rename = "DiskType")]
+    pub disk_type: String,
+    #[serde(rename = "DiskSize")]
+    pub disk_size: u32,
+    // UNATTACHED / ATTACHING / ATTACHED / DETACHING / EXPANDING / ROLLBACKING / TORECYCLE / DUMPING
+    #[serde(rename = "DiskState")]
+    pub disk_state: String,
+    #[serde(rename = "DiskChargeType")]
+    pub disk_charge_type: String,
+    #[serde(rename = "Placement")]
+    pub placement: TcsPlacement,
+    #[serde(rename = "Portable")]
+    pub portable: bool,
+    #[serde(rename = "Attached")]
+    pub attached: bool,
+    #[serde(rename = "InstanceId")]
+    pub instance_id: Option<String>,
+    #[serde(rename = "DeleteWithInstance")]
+    pub delete_with_instance: Option<bool>,
+    #[serde(rename = "AutoSnapshotPolicyIds", default, deserialize_with = "crate::null_default")]
+    pub auto_snapshot_policy_ids: Vec<String>,
+    #[serde(rename = "CreateTime")]
+    pub create_time: Option<String>,
+    #[serde(rename = "Tags", default, deserialize_with = "crate::null_default")]
+    pub tags: Vec<TcsTag>,
+}
+
+// 创建云硬盘参数
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsCreateDisksRequest {
+    #[serde(rename = "Placement")]
+    pub placement: TcsPlacement,
+    // POSTPAID_BY_HOUR / PREPAID
+    #[serde(rename = "DiskChargeType")]
+    pub disk_charge_type: String,
+    // CLOUD_BASIC / CLOUD_PREMIUM / CLOUD_SSD / CLOUD_HSSD / CLOUD_TSSD
+    #[serde(rename = "DiskType")]
+    pub disk_type: String,
+    #[serde(rename = "DiskSize")]
+    pub disk_size: u32,
+    #[serde(rename = "DiskName", skip_serializing_if = "Option::is_none")]
+    pub disk_name: Option<String>,
+    #[serde(rename = "DiskCount", skip_serializing_if = "Option::is_none")]
+    pub disk_count: Option<u32>,
+    #[serde(rename = "SnapshotId", skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<String>,
+    #[serde(rename = "Tags", default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<TcsTag>,
+}
+
+impl TcsCreateDisksRequest {
+    // 按量计费数据盘
+    pub fn new(zone: &str, disk_type: &str, disk_size: u32, disk_name: &str) -> Self {
+        TcsCreateDisksRequest {
+            placement: TcsPlacement { zone: zone.to_string() },
+            disk_charge_type: "POSTPAID_BY_HOUR".to_string(),
+            disk_type: disk_type.to_string(),
+            disk_size,
+            disk_name: Some(disk_name.to_string()),
+            disk_count: None,
+            snapshot_id: None,
+            tags: vec![],
+        }
+    }
+}
+
+// 快照
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsSnapshot {
+    #[serde(rename = "SnapshotId")]
+    pub snapshot_id: String,
+    #[serde(rename = "SnapshotName")]
+    pub snapshot_name: String,
+    // NORMAL / CREATING / ROLLBACKING / COPYING_FROM_REMOTE / CHECKING_COPIED / TORECYCLE
+    #[serde(rename = "SnapshotState")]
+    pub snapshot_state: String,
+    #[serde(rename = "DiskId")]
+    pub disk_id: String,
+    #[serde(rename = "DiskSize")]
+    pub disk_size: u32,
+    #[serde(rename = "DiskUsage")]
+    pub disk_usage: Option<String>,
+    #[serde(rename = "Percent")]
+    pub percent: Option<u32>,
+    #[serde(rename = "Placement")]
+    pub placement: Option<TcsPlacement>,
+    #[serde(rename = "IsPermanent")]
+    pub is_permanent: Option<bool>,
+    #[serde(rename = "DeadlineTime")]
+    pub deadline_time: Option<String>,
+    #[serde(rename = "CreateTime")]
+    pub create_time: String,
+}
+
+// 定期快照执行时间
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsSnapshotSchedule {
+    // 0 - 6, 0 为周日
+    #[serde(rename = "DayOfWeek", default, skip_serializing_if = "Vec::is_empty")]
+    pub day_of_week: Vec<u32>,
+    // 0 - 23
+    #[serde(rename = "Hour")]
+    pub hour: Vec<u32>,
+    #[serde(rename = "DayOfMonth", default, skip_serializing_if = "Vec::is_empty")]
+    pub day_of_month: Vec<u32>,
+    #[serde(rename = "IntervalDays", skip_serializing_if = "Option::is_none")]
+    pub interval_days: Option<u32>,
+}
+
+// 定期快照策略
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsAutoSnapshotPolicy {
+    #[serde(rename = "AutoSnapshotPolicyId")]
+    pub auto_snapshot_policy_id: String,
+    #[serde(rename = "AutoSnapshotPolicyName")]
+    pub auto_snapshot_policy_name: String,
+    #[serde(rename = "AutoSnapshotPolicyState")]
+    pub auto_snapshot_policy_state: String,
+    #[serde(rename = "IsActivated")]
+    pub is_activated: bool,
+    #[serde(rename = "IsPermanent")]
+    pub is_permanent: bool,
+    #[serde(rename = "RetentionDays")]
+    pub retention_days: u32,
+    #[serde(rename = "Policy", default, deserialize_with = "crate::null_default")]
+    pub policy: Vec<TcsSnapshotSchedule>,
+    #[serde(rename = "DiskIdSet", default, deserialize_with = "crate::null_default")]
+    pub disk_id_set: Vec<String>,
+    #[serde(rename = "NextTriggerTime")]
+    pub next_trigger_time: Option<String>,
+    #[serde(rename = "CreateTime")]
+    pub create_time: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeDisks {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "DiskSet")]
+    pub disk_set: Vec<TcsCbsDisk>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateDisks {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "DiskIdSet")]
+    pub disk_id_set: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeSnapshots {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "SnapshotSet")]
+    pub snapshot_set: Vec<TcsSnapshot>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateSnapshot {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "SnapshotId")]
+    pub snapshot_id: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeAutoSnapshotPolicies {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "AutoSnapshotPolicySet")]
+    pub auto_snapshot_policy_set: Vec<TcsAutoSnapshotPolicy>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateAutoSnapshotPolicy {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "AutoSnapshotPolicyId")]
+    pub auto_snapshot_policy_id: String,
+    #[serde(rename = "NextTriggerTime")]
+    pub next_trigger_time: Option<String>,
+}
+
+impl TencentCloudApi {
+    // 查询云硬盘列表
+    pub fn tcs_describe_disks(&mut self, tcs_region: &str, disk_ids: &[String], filters: &[TcsFilter]) -> Result<Vec<TcsCbsDisk>, Box<dyn Error>> {
+        info!("[######][查询云硬盘列表][@][tcs_describe_disks()][tcs_region: {}][disk_ids: {:?}][filters: {:?}]", tcs_region, disk_ids, filters);
+
+        let limit = 100;
+        let mut disk_set: Vec<TcsCbsDisk> = vec![];
+        loop {
+            let mut payload = json!({
+                "Offset": disk_set.len(),
+                "Limit": limit
+            });
+            // ID 列表与 Filters 不能同时指定
+            if !disk_ids.is_empty() {
+                payload["DiskIds"] = json!(disk_ids);
+            } else if !filters.is_empty() {
+                payload["Filters"] = json!(filters);
+            }
+
+            let tcs_response_data: TcsResponseDescribeDisks = self.tcs_request_service_action(&TCS_SERVICE_CBS, "DescribeDisks", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.disk_set.len();
+            disk_set.extend(tcs_response_data.disk_set);
+
+            if page_count == 0 || disk_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(disk_set)
+    }
+
+    // 等待云硬盘状态
+    pub fn tcs_wait_disk_state(&mut self, tcs_region: &str, disk_id: &str, disk_state: &str, waiter: &TcsWaiter) -> Result<TcsCbsDisk, Box<dyn Error>> {
+        info!("[######][等待云硬盘状态][@][tcs_wait_disk_state()][tcs_region: {}][disk_id: {}][disk_state: {}]", tcs_region, disk_id, disk_state);
+
+        let tcs_target = format!("{}:{}", disk_id, disk_state);
+        let disk_ids = [disk_id.to_string()];
+        waiter.wait(tcs_target.as_str(), || {
+            let disk_set = self.tcs_describe_disks(tcs_region, &disk_ids, &[])?;
+            Ok(disk_set.into_iter().find(|disk| disk.disk_state == disk_state))
+        })
+    }
+
+    // 创建云硬盘
+    pub fn tcs_create_disks(&mut self, tcs_region: &str, request: &TcsCreateDisksRequest) -> Result<Vec<String>, Box<dyn Error>> {
+        info!("[######][创建云硬盘][@][tcs_create_disks()][tcs_region: {}][request: {:?}]", tcs_region, request);
+
+        let payload = serde_json::to_value(request)?;
+        let tcs_response_data: TcsResponseCreateDisks = self.tcs_request_service_action(&TCS_SERVICE_CBS, "CreateDisks", tcs_region, &payload)?;
+        Ok(tcs_response_data.disk_id_set)
+    }
+
+    // 挂载云硬盘(云硬盘与实例须在同一可用区)
+    pub fn tcs_attach_disks(&mut self, tcs_region: &str, disk_ids: &[String], instance_id: &str, delete_with_instance: bool) -> Result<(), Box<dyn Error>> {
+        info!(
+            "[######][挂载云硬盘][@][tcs_attach_disks()][tcs_region: {}][disk_ids: {:?}][instance_id: {}][delete_with_instance: {}]",
+            tcs_region, disk_ids, instance_id, delete_with_instance
+        );
+
+        let payload = json!({
+            "DiskIds": disk_ids,
+            "InstanceId": instance_id,
+            "DeleteWithInstance": delete_with_instance
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_CBS, "AttachDisks", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 卸载云硬盘
+    pub fn tcs_detach_disks(&mut self, tcs_region: &str, disk_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!("[######][卸载云硬盘][@][tcs_detach_disks()][tcs_region: {}][disk_ids: {:?}]", tcs_region, disk_ids);
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_CBS, "DetachDisks", tcs_region, &json!({ "DiskIds": disk_ids }))?;
+        Ok(())
+    }
+
+    // 扩容云硬盘(只能扩大)
+    pub fn tcs_resize_disk(&mut self, tcs_region: &str, disk_id: &str, disk_size: u32) -> Result<(), Box<dyn Error>> {
+        info!("[######][扩容云硬盘][@][tcs_resize_disk()][tcs_region: {}][disk_id: {}][disk_size: {}]", tcs_region, disk_id, disk_size);
+
+        let payload = json!({
+            "DiskId": disk_id,
+            "DiskSize": disk_size
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_CBS, "ResizeDisk", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 退还云硬盘(须先卸载)
+    pub fn tcs_terminate_disks(&mut self, tcs_region: &str, disk_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!("[######][退还云硬盘][@][tcs_terminate_disks()][tcs_region: {}][disk_ids: {:?}]", tcs_region, disk_ids);
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_CBS, "TerminateDisks", tcs_region, &json!({ "DiskIds": disk_ids }))?;
+        Ok(())
+    }
+
+    // 将数据盘迁移到新实例(例如竞价实例被回收后), 已挂载到新实例时不做处理
+    pub fn tcs_move_disk(&mut self, tcs_region: &str, disk_id: &str, instance_id: &str, waiter: &TcsWaiter) -> Result<TcsCbsDisk, Box<dyn Error>> {
+        info!("[######][迁移云硬盘][@][tcs_move_disk()][tcs_region: {}][disk_id: {}][instance_id: {}]", tcs_region, disk_id, instance_id);
+
+        let disk_ids = [disk_id.to_string()];
+        let disk = self.tcs_describe_disks(tcs_region, &disk_ids, &[])?.into_iter().next().ok_or_else(|| format!("云硬盘不存在({})!", disk_id))?;
+        if disk.attached && disk.instance_id.as_deref() == Some(instance_id) {
+            return Ok(disk);
+        }
+        if !disk.portable {
+            return Result::Err(format!("云硬盘不支持卸载({})!", disk_id).into());
+        }
+
+        if disk.attached {
+            info!("[卸载旧实例][disk_id: {}][instance_id: {:?}]", disk_id, disk.instance_id);
+            self.tcs_detach_disks(tcs_region, &disk_ids)?;
+        }
+        if disk.disk_state != "UNATTACHED" {
+            self.tcs_wait_disk_state(tcs_region, disk_id, "UNATTACHED", waiter)?;
+        }
+
+        // 数据需要在实例回收后保留...
+        self.tcs_attach_disks(tcs_region, &disk_ids, instance_id, false)?;
+        self.tcs_wait_disk_state(tcs_region, disk_id, "ATTACHED", waiter)
+    }
+
+    // 查询快照列表
+    pub fn tcs_describe_snapshots(&mut self, tcs_region: &str, snapshot_ids: &[String], filters: &[TcsFilter]) -> Result<Vec<TcsSnapshot>, Box<dyn Error>> {
+        info!("[######][查询快照列表][@][tcs_describe_snapshots()][tcs_region: {}][snapshot_ids: {:?}][filters: {:?}]", tcs_region, snapshot_ids, filters);
+
+        let limit = 100;
+        let mut snapshot_set: Vec<TcsSnapshot> = vec![];
+        loop {
+            let mut payload = json!({
+                "Offset": snapshot_set.len(),
+                "Limit": limit
+            });
+            // ID 列表与 Filters 不能同时指定
+            if !snapshot_ids.is_empty() {
+                payload["SnapshotIds"] = json!(snapshot_ids);
+            } else if !filters.is_empty() {
+                payload["Filters"] = json!(filters);
+            }
+
+            let tcs_response_data: TcsResponseDescribeSnapshots = self.tcs_request_service_action(&TCS_SERVICE_CBS, "DescribeSnapshots", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.snapshot_set.len();
+            snapshot_set.extend(tcs_response_data.snapshot_set);
+
+            if page_count == 0 || snapshot_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(snapshot_set)
+    }
+
+    // 创建快照, 指定 waiter 时等待快照可用
+    pub fn tcs_create_snapshot(&mut self, tcs_region: &str, disk_id: &str, snapshot_name: &str, waiter: Option<&TcsWaiter>) -> Result<String, Box<dyn Error>> {
+        info!("[######][创建快照][@][tcs_create_snapshot()][tcs_region: {}][disk_id: {}][snapshot_name: {}]", tcs_region, disk_id, snapshot_name);
+
+        let payload = json!({
+            "DiskId": disk_id,
+            "SnapshotName": snapshot_name
+        });
+
+        let tcs_response_data: TcsResponseCreateSnapshot = self.tcs_request_service_action(&TCS_SERVICE_CBS, "CreateSnapshot", tcs_region, &payload)?;
+        let snapshot_id = tcs_response_data.snapshot_id;
+
+        if let Some(waiter) = waiter {
+            self.tcs_wait_snapshot_state(tcs_region, snapshot_id.as_str(), "NORMAL", waiter)?;
+        }
+
+        Ok(snapshot_id)
+    }
+
+    // 等待快照状态
+    pub fn tcs_wait_snapshot_state(&mut self, tcs_region: &str, snapshot_id: &str, snapshot_state: &str, waiter: &TcsWaiter) -> Result<TcsSnapshot, Box<dyn Error>> {
+        info!("[######][等待快照状态][@][tcs_wait_snapshot_state()][tcs_region: {}][snapshot_id: {}][snapshot_state: {}]", tcs_region, snapshot_id, snapshot_state);
+
+        let tcs_target = format!("{}:{}", snapshot_id, snapshot_state);
+        let snapshot_ids = [snapshot_id.to_string()];
+        waiter.wait(tcs_target.as_str(), || {
+            let snapshot_set = self.tcs_describe_snapshots(tcs_region, &snapshot_ids, &[])?;
+            Ok(snapshot_set.into_iter().find(|snapshot| snapshot.snapshot_state == snapshot_state))
+        })
+    }
+
+    // 删除快照
+    pub fn tcs_delete_snapshots(&mut self, tcs_region: &str, snapshot_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除快照][@][tcs_delete_snapshots()][tcs_region: {}][snapshot_ids: {:?}]", tcs_region, snapshot_ids);
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_CBS, "DeleteSnapshots", tcs_region, &json!({ "SnapshotIds": snapshot_ids }))?;
+        Ok(())
+    }
+
+    // 查询定期快照策略
+    pub fn tcs_describe_auto_snapshot_policies(&mut self, tcs_region: &str, auto_snapshot_policy_ids: &[String]) -> Result<Vec<TcsAutoSnapshotPolicy>, Box<dyn Error>> {
+        info!("[######][查询定期快照策略][@][tcs_describe_auto_snapshot_policies()][tcs_region: {}][auto_snapshot_policy_ids: {:?}]", tcs_region, auto_snapshot_policy_ids);
+
+        let limit = 100;
+        let mut policy_set: Vec<TcsAutoSnapshotPolicy> = vec![];
+        loop {
+            let mut payload = json!({
+                "Offset": policy_set.len(),
+                "Limit": limit
+            });
+            if !auto_snapshot_policy_ids.is_empty() {
+                payload["AutoSnapshotPolicyIds"] = json!(auto_snapshot_policy_ids);
+            }
+
+            let tcs_response_data: TcsResponseDescribeAutoSnapshotPolicies = self.tcs_request_service_action(&TCS_SERVICE_CBS, "DescribeAutoSnapshotPolicies", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.auto_snapshot_policy_set.len();
+            policy_set.extend(tcs_response_data.auto_snapshot_policy_set);
+
+            if page_count == 0 || policy_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(policy_set)
+    }
+
+    // 创建定期快照策略
+    pub fn tcs_create_auto_snapshot_policy(&mut self, tcs_region: &str, policy_name: &str, schedules: &[TcsSnapshotSchedule], retention_days: u32) -> Result<String, Box<dyn Error>> {
+        info!(
+            "[######][创建定期快照策略][@][tcs_create_auto_snapshot_policy()][tcs_region: {}][policy_name: {}][schedules: {:?}][retention_days: {}]",
+            tcs_region, policy_name, schedules, retention_days
+        );
+
+        let payload = json!({
+            "AutoSnapshotPolicyName": policy_name,
+            "Policy": schedules,
+            "IsActivated": true,
+            "IsPermanent": false,
+            "RetentionDays": retention_days
+        });
+
+        let tcs_response_data: TcsResponseCreateAutoSnapshotPolicy = self.tcs_request_service_action(&TCS_SERVICE_CBS, "CreateAutoSnapshotPolicy", tcs_region, &payload)?;
+        Ok(tcs_response_data.auto_snapshot_policy_id)
+    }
+
+    // 删除定期快照策略
+    pub fn tcs_delete_auto_snapshot_policies(&mut self, tcs_region: &str, auto_snapshot_policy_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除定期快照策略][@][tcs_delete_auto_snapshot_policies()][tcs_region: {}][auto_snapshot_policy_ids: {:?}]", tcs_region, auto_snapshot_policy_ids);
+
+        let payload = json!({ "AutoSnapshotPolicyIds": auto_snapshot_policy_ids });
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_CBS, "DeleteAutoSnapshotPolicies", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 云硬盘绑定定期快照策略
+    pub fn tcs_bind_auto_snapshot_policy(&mut self, tcs_region: &str, auto_snapshot_policy_id: &str, disk_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!(
+            "[######][绑定定期快照策略][@][tcs_bind_auto_snapshot_policy()][tcs_region: {}][auto_snapshot_policy_id: {}][disk_ids: {:?}]",
+            tcs_region, auto_snapshot_policy_id, disk_ids
+        );
+
+        let payload = json!({
+            "AutoSnapshotPolicyId": auto_snapshot_policy_id,
+            "DiskIds": disk_ids
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_CBS, "BindAutoSnapshotPolicy", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 云硬盘解绑定期快照策略
+    pub fn tcs_unbind_auto_snapshot_policy(&mut self, tcs_region: &str, auto_snapshot_policy_id: &str, disk_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!(
+            "[######][解绑定期快照策略][@][tcs_unbind_auto_snapshot_policy()][tcs_region: {}][auto_snapshot_policy_id: {}][disk_ids: {:?}]",
+            tcs_region, auto_snapshot_policy_id, disk_ids
+        );
+
+        let payload = json!({
+            "AutoSnapshotPolicyId": auto_snapshot_policy_id,
+            "DiskIds": disk_ids
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_CBS, "UnbindAutoSnapshotPolicy", tcs_region, &payload)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn create_disks_payload() {
+        let mut request = TcsCreateDisksRequest::new("ap-guangzhou-3", "CLOUD_SSD", 100, "data-web-0");
+        request.tags = vec![TcsTag::new("tcs-fleet", "web")];
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "Placement": { "Zone": "ap-guangzhou-3" },
+                "DiskChargeType": "POSTPAID_BY_HOUR",
+                "DiskType": "CLOUD_SSD",
+                "DiskSize": 100,
+                "DiskName": "data-web-0",
+                "Tags": [{ "Key": "tcs-fleet", "Value": "web" }]
+            })
+        );
+
+        let schedule = TcsSnapshotSchedule {
+            day_of_week: vec![1, 3, 5],
+            hour: vec![2],
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_value(&schedule).unwrap(), json!({ "DayOfWeek": [1, 3, 5], "Hour": [2] }));
+    }
+}
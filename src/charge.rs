@@ -0,0 +1,193 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use std::error::Error;
+
+use crate::{TcsInstanceTypeQuotaPrice, TcsItemPrice, TcsPrice, TcsResponseEmpty, TcsResponseInquiryPrice, TcsTotalPrice, TcsWaiter, TencentCloudApi};
+
+// 单次请求最多支持的实例数量
+const TCS_INSTANCE_BATCH: usize = 100;
+
+// 包年包月参数
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsInstanceChargePrepaid {
+    // 购买时长(月)
+    #[serde(rename = "Period")]
+    pub period: u32,
+    // NOTIFY_AND_AUTO_RENEW / NOTIFY_AND_MANUAL_RENEW / DISABLE_NOTIFY_AND_MANUAL_RENEW
+    #[serde(rename = "RenewFlag", skip_serializing_if = "Option::is_none")]
+    pub renew_flag: Option<String>,
+}
+
+impl TcsInstanceChargePrepaid {
+    pub fn new(period: u32, renew_flag: Option<&str>) -> Self {
+        TcsInstanceChargePrepaid {
+            period,
+            renew_flag: renew_flag.map(|renew_flag| renew_flag.to_string()),
+        }
+    }
+}
+
+// 续费实例/续费询价参数
+fn renew_instances_payload(instance_ids: &[String], instance_charge_prepaid: &TcsInstanceChargePrepaid, renew_portable_data_disk: bool) -> Value {
+    json!({
+        "InstanceIds": instance_ids,
+        "InstanceChargePrepaid": instance_charge_prepaid,
+        "RenewPortableDataDisk": renew_portable_data_disk
+    })
+}
+
+// 修改实例计费模式参数
+fn modify_instances_charge_type_payload(instance_ids: &[String], instance_charge_prepaid: &TcsInstanceChargePrepaid) -> Value {
+    json!({
+        "InstanceIds": instance_ids,
+        "InstanceChargeType": "PREPAID",
+        "InstanceChargePrepaid": instance_charge_prepaid
+    })
+}
+
+// 折扣, 例如 80.0 表示八折
+fn price_discount(discounted: f32, original: f32) -> f32 {
+    if original > 0.0 { discounted / original * 100.0 } else { 100.0 }
+}
+
+// 合并分批询价的价格
+fn sum_item_prices(total: Option<TcsItemPrice>, item_price: Option<TcsItemPrice>) -> Result<Option<TcsItemPrice>, Box<dyn Error>> {
+    let item_price = match (total, item_price) {
+        (None, item_price) | (item_price, None) => item_price,
+        (Some(TcsItemPrice::Total(total)), Some(TcsItemPrice::Total(item_price))) => {
+            let original_price = total.original_price + item_price.original_price;
+            let discount_price = total.discount_price + item_price.discount_price;
+            Some(TcsItemPrice::Total(TcsTotalPrice {
+                original_price,
+                discount_price,
+                discount: price_discount(discount_price, original_price),
+            }))
+        }
+        (Some(TcsItemPrice::Unit(total)), Some(TcsItemPrice::Unit(item_price))) if total.charge_unit == item_price.charge_unit => {
+            let unit_price = total.unit_price + item_price.unit_price;
+            let unit_price_discount = total.unit_price_discount + item_price.unit_price_discount;
+            Some(TcsItemPrice::Unit(TcsInstanceTypeQuotaPrice {
+                unit_price,
+                unit_price_discount,
+                discount: price_discount(unit_price_discount, unit_price),
+                charge_unit: total.charge_unit,
+            }))
+        }
+        _ => return Result::Err("询价结果的计费方式不一致!".into()),
+    };
+    Ok(item_price)
+}
+
+impl TencentCloudApi {
+    // 修改实例计费模式(按量计费 -> 包年包月)
+    // [修改实例计费模式](https://cloud.tencent.com/document/api/213/56341)
+    pub fn tcs_modify_instances_charge_type(&mut self, tcs_region: &str, instance_ids: &[String], instance_charge_prepaid: &TcsInstanceChargePrepaid, waiter: Option<&TcsWaiter>) -> Result<(), Box<dyn Error>> {
+        info!("[######][修改实例计费模式][@][tcs_modify_instances_charge_type()][tcs_region: {}][instance_ids: {:?}][instance_charge_prepaid: {:?}]", tcs_region, instance_ids, instance_charge_prepaid);
+
+        for instance_ids in instance_ids.chunks(TCS_INSTANCE_BATCH) {
+            let payload = modify_instances_charge_type_payload(instance_ids, instance_charge_prepaid);
+
+            let tcs_response_data: TcsResponseEmpty = self.tcs_request_action("ModifyInstancesChargeType", tcs_region, &payload)?;
+            self.tcs_wait_instances_operation(tcs_region, instance_ids, tcs_response_data.request_id.as_str(), waiter)?;
+        }
+
+        Ok(())
+    }
+
+    // 续费实例
+    pub fn tcs_renew_instances(&mut self, tcs_region: &str, instance_ids: &[String], instance_charge_prepaid: &TcsInstanceChargePrepaid, renew_portable_data_disk: bool) -> Result<(), Box<dyn Error>> {
+        info!("[######][续费实例][@][tcs_renew_instances()][tcs_region: {}][instance_ids: {:?}][instance_charge_prepaid: {:?}]", tcs_region, instance_ids, instance_charge_prepaid);
+
+        for instance_ids in instance_ids.chunks(TCS_INSTANCE_BATCH) {
+            let payload = renew_instances_payload(instance_ids, instance_charge_prepaid, renew_portable_data_disk);
+
+            let _: TcsResponseEmpty = self.tcs_request_action("RenewInstances", tcs_region, &payload)?;
+        }
+
+        Ok(())
+    }
+
+    // 修改实例续费标识
+    pub fn tcs_modify_instances_renew_flag(&mut self, tcs_region: &str, instance_ids: &[String], renew_flag: &str) -> Result<(), Box<dyn Error>> {
+        info!("[######][修改实例续费标识][@][tcs_modify_instances_renew_flag()][tcs_region: {}][instance_ids: {:?}][renew_flag: {}]", tcs_region, instance_ids, renew_flag);
+
+        for instance_ids in instance_ids.chunks(TCS_INSTANCE_BATCH) {
+            let payload = json!({
+                "InstanceIds": instance_ids,
+                "RenewFlag": renew_flag
+            });
+
+            let _: TcsResponseEmpty = self.tcs_request_action("ModifyInstancesRenewFlag", tcs_region, &payload)?;
+        }
+
+        Ok(())
+    }
+
+    // 续费实例询价(分批询价后合计)
+    pub fn tcs_inquiry_price_renew_instances(&mut self, tcs_region: &str, instance_ids: &[String], instance_charge_prepaid: &TcsInstanceChargePrepaid, renew_portable_data_disk: bool) -> Result<TcsPrice, Box<dyn Error>> {
+        info!("[######][续费实例询价][@][tcs_inquiry_price_renew_instances()][tcs_region: {}][instance_ids: {:?}][instance_charge_prepaid: {:?}]", tcs_region, instance_ids, instance_charge_prepaid);
+
+        let mut price = TcsPrice {
+            instance_price: None,
+            bandwidth_price: None,
+        };
+        for instance_ids in instance_ids.chunks(TCS_INSTANCE_BATCH) {
+            let payload = renew_instances_payload(instance_ids, instance_charge_prepaid, renew_portable_data_disk);
+
+            let tcs_response_data: TcsResponseInquiryPrice = self.tcs_request_action("InquiryPriceRenewInstances", tcs_region, &payload)?;
+            info!("[tcs_response_data: {:?}]", tcs_response_data);
+
+            price.instance_price = sum_item_prices(price.instance_price, tcs_response_data.price.instance_price)?;
+            price.bandwidth_price = sum_item_prices(price.bandwidth_price, tcs_response_data.price.bandwidth_price)?;
+        }
+
+        Ok(price)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn renew_and_charge_type_payloads() {
+        let instance_ids = vec!["ins-1".to_string(), "ins-2".to_string()];
+        let instance_charge_prepaid = TcsInstanceChargePrepaid::new(3, Some("NOTIFY_AND_AUTO_RENEW"));
+
+        let payload = renew_instances_payload(&instance_ids, &instance_charge_prepaid, false);
+        assert_eq!(
+            payload,
+            json!({
+                "InstanceIds": ["ins-1", "ins-2"],
+                "InstanceChargePrepaid": { "Period": 3, "RenewFlag": "NOTIFY_AND_AUTO_RENEW" },
+                "RenewPortableDataDisk": false
+            })
+        );
+
+        let payload = modify_instances_charge_type_payload(&instance_ids, &TcsInstanceChargePrepaid::new(1, None));
+        assert_eq!(
+            payload,
+            json!({
+                "InstanceIds": ["ins-1", "ins-2"],
+                "InstanceChargeType": "PREPAID",
+                "InstanceChargePrepaid": { "Period": 1 }
+            })
+        );
+    }
+
+    #[test]
+    fn sum_renew_prices() {
+        let batch: TcsPrice = serde_json::from_value(json!({ "InstancePrice": { "OriginalPrice": 300.0, "DiscountPrice": 240.0, "Discount": 80.0 } })).unwrap();
+        let other: TcsPrice = serde_json::from_value(json!({ "InstancePrice": { "OriginalPrice": 100.0, "DiscountPrice": 100.0, "Discount": 100.0 } })).unwrap();
+
+        let instance_price = sum_item_prices(batch.instance_price.clone(), other.instance_price).unwrap().unwrap();
+        assert_eq!(instance_price.discounted_total_price(), Some(340.0));
+        assert_eq!(instance_price.discount(), 85.0);
+        assert!(sum_item_prices(None, None).unwrap().is_none());
+
+        let unit: TcsPrice = serde_json::from_value(json!({ "InstancePrice": { "UnitPrice": 0.5, "UnitPriceDiscount": 0.5, "Discount": 100.0, "ChargeUnit": "HOUR" } })).unwrap();
+        assert!(sum_item_prices(batch.instance_price, unit.instance_price).is_err());
+    }
+}
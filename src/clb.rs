@@ -0,0 +1,430 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+use crate::{TCS_SERVICE_CLB, TcsTag, TcsWaiter, TencentCloudApi};
+
+// 负载均衡实例
+// [查询负载均衡实例列表](https://cloud.tencent.com/document/api/214/30685)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsLoadBalancer {
+    #[serde(rename = "LoadBalancerId")]
+    pub load_balancer_id: String,
+    #[serde(rename = "LoadBalancerName")]
+    pub load_balancer_name: String,
+    // OPEN / INTERNAL
+    #[serde(rename = "LoadBalancerType")]
+    pub load_balancer_type: String,
+    #[serde(rename = "LoadBalancerVips", default, deserialize_with = "crate::null_default")]
+    pub load_balancer_vips: Vec<String>,
+    // 0: 创建中, 1: 正常运行
+    #[serde(rename = "Status")]
+    pub status: Option<u32>,
+    #[serde(rename = "VpcId")]
+    pub vpc_id: Option<String>,
+    #[serde(rename = "Domain")]
+    pub domain: Option<String>,
+    #[serde(rename = "CreateTime")]
+    pub create_time: Option<String>,
+    #[serde(rename = "Tags", default, deserialize_with = "crate::null_default")]
+    pub tags: Vec<TcsTag>,
+}
+
+// 监听器
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsListener {
+    #[serde(rename = "ListenerId")]
+    pub listener_id: String,
+    #[serde(rename = "ListenerName")]
+    pub listener_name: Option<String>,
+    // TCP / UDP / TCP_SSL / HTTP / HTTPS
+    #[serde(rename = "Protocol")]
+    pub protocol: String,
+    #[serde(rename = "Port")]
+    pub port: u32,
+    // 七层监听器的转发规则
+    #[serde(rename = "Rules", default, deserialize_with = "crate::null_default")]
+    pub rules: Vec<TcsListenerRule>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsListenerRule {
+    #[serde(rename = "LocationId")]
+    pub location_id: String,
+    #[serde(rename = "Domain")]
+    pub domain: String,
+    #[serde(rename = "Url")]
+    pub url: String,
+}
+
+// 后端服务
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsClbTarget {
+    #[serde(rename = "InstanceId")]
+    pub instance_id: String,
+    #[serde(rename = "Port")]
+    pub port: u32,
+    #[serde(rename = "Weight", skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+}
+
+impl TcsClbTarget {
+    pub fn new(instance_id: &str, port: u32, weight: Option<u32>) -> Self {
+        TcsClbTarget {
+            instance_id: instance_id.to_string(),
+            port,
+            weight,
+        }
+    }
+}
+
+// 后端服务所在位置: 负载均衡 + 监听器 (+ 七层转发规则)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsClbTargetGroup {
+    pub load_balancer_id: String,
+    pub listener_id: String,
+    pub location_id: Option<String>,
+}
+
+impl TcsClbTargetGroup {
+    fn payload(&self) -> serde_json::Value {
+        let mut payload = json!({
+            "LoadBalancerId": self.load_balancer_id,
+            "ListenerId": self.listener_id
+        });
+        if let Some(location_id) = &self.location_id {
+            payload["LocationId"] = json!(location_id);
+        }
+        payload
+    }
+}
+
+// 后端服务健康状态
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsTargetHealth {
+    #[serde(rename = "IP")]
+    pub ip: String,
+    #[serde(rename = "Port")]
+    pub port: u32,
+    #[serde(rename = "HealthStatus")]
+    pub health_status: bool,
+    #[serde(rename = "TargetId")]
+    pub target_id: String,
+    #[serde(rename = "HealthStatusDetail")]
+    pub health_status_detail: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsRuleHealth {
+    #[serde(rename = "LocationId")]
+    pub location_id: Option<String>,
+    #[serde(rename = "Domain")]
+    pub domain: Option<String>,
+    #[serde(rename = "Url")]
+    pub url: Option<String>,
+    #[serde(rename = "Targets", default, deserialize_with = "crate::null_default")]
+    pub targets: Vec<TcsTargetHealth>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsListenerHealth {
+    #[serde(rename = "ListenerId")]
+    pub listener_id: String,
+    #[serde(rename = "Protocol")]
+    pub protocol: String,
+    #[serde(rename = "Port")]
+    pub port: u32,
+    #[serde(rename = "Rules", default, deserialize_with = "crate::null_default")]
+    pub rules: Vec<TcsRuleHealth>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsLoadBalancerHealth {
+    #[serde(rename = "LoadBalancerId")]
+    pub load_balancer_id: String,
+    #[serde(rename = "LoadBalancerName")]
+    pub load_balancer_name: Option<String>,
+    #[serde(rename = "Listeners", default, deserialize_with = "crate::null_default")]
+    pub listeners: Vec<TcsListenerHealth>,
+}
+
+impl TcsLoadBalancerHealth {
+    // 指定位置下的后端服务健康状态
+    pub fn targets(&self, target_group: &TcsClbTargetGroup) -> Vec<&TcsTargetHealth> {
+        self.listeners
+            .iter()
+            .filter(|listener| listener.listener_id == target_group.listener_id)
+            .flat_map(|listener| listener.rules.iter())
+            .filter(|rule| target_group.location_id.is_none() || rule.location_id == target_group.location_id)
+            .flat_map(|rule| rule.targets.iter())
+            .collect()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeLoadBalancers {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "LoadBalancerSet")]
+    pub load_balancer_set: Vec<TcsLoadBalancer>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeListeners {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "Listeners")]
+    pub listeners: Vec<TcsListener>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeTargetHealth {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "LoadBalancers")]
+    pub load_balancers: Vec<TcsLoadBalancerHealth>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeTaskStatus {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    // 0: 成功, 1: 失败, 2: 进行中
+    #[serde(rename = "Status")]
+    pub status: u32,
+}
+
+// 异步操作的 RequestId 即任务ID
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseClbTask {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+}
+
+impl TencentCloudApi {
+    // 查询负载均衡实例列表
+    pub fn tcs_describe_load_balancers(&mut self, tcs_region: &str, load_balancer_ids: &[String], load_balancer_name: Option<&str>) -> Result<Vec<TcsLoadBalancer>, Box<dyn Error>> {
+        info!(
+            "[######][查询负载均衡列表][@][tcs_describe_load_balancers()][tcs_region: {}][load_balancer_ids: {:?}][load_balancer_name: {:?}]",
+            tcs_region, load_balancer_ids, load_balancer_name
+        );
+
+        let limit = 100;
+        let mut load_balancer_set: Vec<TcsLoadBalancer> = vec![];
+        loop {
+            let mut payload = json!({
+                "Offset": load_balancer_set.len(),
+                "Limit": limit
+            });
+            if !load_balancer_ids.is_empty() {
+                payload["LoadBalancerIds"] = json!(load_balancer_ids);
+            }
+            if let Some(load_balancer_name) = load_balancer_name {
+                payload["LoadBalancerName"] = json!(load_balancer_name);
+            }
+
+            let tcs_response_data: TcsResponseDescribeLoadBalancers = self.tcs_request_service_action(&TCS_SERVICE_CLB, "DescribeLoadBalancers", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.load_balancer_set.len();
+            load_balancer_set.extend(tcs_response_data.load_balancer_set);
+
+            if page_count == 0 || load_balancer_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(load_balancer_set)
+    }
+
+    // 查询监听器列表
+    pub fn tcs_describe_listeners(&mut self, tcs_region: &str, load_balancer_id: &str, listener_ids: &[String]) -> Result<Vec<TcsListener>, Box<dyn Error>> {
+        info!("[######][查询监听器列表][@][tcs_describe_listeners()][tcs_region: {}][load_balancer_id: {}][listener_ids: {:?}]", tcs_region, load_balancer_id, listener_ids);
+
+        let mut payload = json!({ "LoadBalancerId": load_balancer_id });
+        if !listener_ids.is_empty() {
+            payload["ListenerIds"] = json!(listener_ids);
+        }
+
+        let tcs_response_data: TcsResponseDescribeListeners = self.tcs_request_service_action(&TCS_SERVICE_CLB, "DescribeListeners", tcs_region, &payload)?;
+        Ok(tcs_response_data.listeners)
+    }
+
+    // 查询后端服务健康状态
+    pub fn tcs_describe_target_health(&mut self, tcs_region: &str, load_balancer_ids: &[String]) -> Result<Vec<TcsLoadBalancerHealth>, Box<dyn Error>> {
+        info!("[######][查询后端健康状态][@][tcs_describe_target_health()][tcs_region: {}][load_balancer_ids: {:?}]", tcs_region, load_balancer_ids);
+
+        let payload = json!({ "LoadBalancerIds": load_balancer_ids });
+        let tcs_response_data: TcsResponseDescribeTargetHealth = self.tcs_request_service_action(&TCS_SERVICE_CLB, "DescribeTargetHealth", tcs_region, &payload)?;
+        Ok(tcs_response_data.load_balancers)
+    }
+
+    // 等待负载均衡异步任务完成
+    pub fn tcs_wait_clb_task(&mut self, tcs_region: &str, task_id: &str, waiter: &TcsWaiter) -> Result<(), Box<dyn Error>> {
+        let payload = json!({ "TaskId": task_id });
+        waiter.wait(task_id, || {
+            let tcs_response_data: TcsResponseDescribeTaskStatus = self.tcs_request_service_action(&TCS_SERVICE_CLB, "DescribeTaskStatus", tcs_region, &payload)?;
+            match tcs_response_data.status {
+                0 => Ok(Some(())),
+                1 => Err(format!("负载均衡任务失败({})!", task_id).into()),
+                _ => Ok(None),
+            }
+        })
+    }
+
+    // 绑定后端服务, 返回任务ID
+    pub fn tcs_register_targets(&mut self, tcs_region: &str, target_group: &TcsClbTargetGroup, targets: &[TcsClbTarget]) -> Result<String, Box<dyn Error>> {
+        info!("[######][绑定后端服务][@][tcs_register_targets()][tcs_region: {}][target_group: {:?}][targets: {:?}]", tcs_region, target_group, targets);
+
+        let mut payload = target_group.payload();
+        payload["Targets"] = json!(targets);
+
+        let tcs_response_data: TcsResponseClbTask = self.tcs_request_service_action(&TCS_SERVICE_CLB, "RegisterTargets", tcs_region, &payload)?;
+        Ok(tcs_response_data.request_id)
+    }
+
+    // 解绑后端服务, 返回任务ID
+    pub fn tcs_deregister_targets(&mut self, tcs_region: &str, target_group: &TcsClbTargetGroup, targets: &[TcsClbTarget]) -> Result<String, Box<dyn Error>> {
+        info!("[######][解绑后端服务][@][tcs_deregister_targets()][tcs_region: {}][target_group: {:?}][targets: {:?}]", tcs_region, target_group, targets);
+
+        // 解绑时不能携带权重...
+        let targets: Vec<TcsClbTarget> = targets.iter().map(|target| TcsClbTarget::new(target.instance_id.as_str(), target.port, None)).collect();
+        let mut payload = target_group.payload();
+        payload["Targets"] = json!(targets);
+
+        let tcs_response_data: TcsResponseClbTask = self.tcs_request_service_action(&TCS_SERVICE_CLB, "DeregisterTargets", tcs_region, &payload)?;
+        Ok(tcs_response_data.request_id)
+    }
+
+    // 修改后端服务权重, 返回任务ID
+    pub fn tcs_modify_target_weight(&mut self, tcs_region: &str, target_group: &TcsClbTargetGroup, targets: &[TcsClbTarget], weight: u32) -> Result<String, Box<dyn Error>> {
+        info!(
+            "[######][修改后端服务权重][@][tcs_modify_target_weight()][tcs_region: {}][target_group: {:?}][targets: {:?}][weight: {}]",
+            tcs_region, target_group, targets, weight
+        );
+
+        let mut payload = target_group.payload();
+        payload["Targets"] = json!(targets);
+        payload["Weight"] = json!(weight);
+
+        let tcs_response_data: TcsResponseClbTask = self.tcs_request_service_action(&TCS_SERVICE_CLB, "ModifyTargetWeight", tcs_region, &payload)?;
+        Ok(tcs_response_data.request_id)
+    }
+
+    // 等待后端服务健康检查通过
+    pub fn tcs_wait_target_healthy(&mut self, tcs_region: &str, target_group: &TcsClbTargetGroup, instance_id: &str, waiter: &TcsWaiter) -> Result<(), Box<dyn Error>> {
+        info!("[######][等待后端服务健康][@][tcs_wait_target_healthy()][tcs_region: {}][target_group: {:?}][instance_id: {}]", tcs_region, target_group, instance_id);
+
+        let load_balancer_ids = [target_group.load_balancer_id.clone()];
+        let tcs_target = format!("{}:healthy", instance_id);
+        waiter.wait(tcs_target.as_str(), || {
+            let load_balancers = self.tcs_describe_target_health(tcs_region, &load_balancer_ids)?;
+            let healthy = load_balancers.iter().flat_map(|load_balancer| load_balancer.targets(target_group)).any(|target| target.target_id == instance_id && target.health_status);
+            Ok(if healthy { Some(()) } else { None })
+        })
+    }
+
+    // 摘除后端服务: 权重置 0, 等待存量连接结束后解绑
+    pub fn tcs_drain_target(&mut self, tcs_region: &str, target_group: &TcsClbTargetGroup, target: &TcsClbTarget, drain_time: Duration, waiter: &TcsWaiter) -> Result<(), Box<dyn Error>> {
+        info!("[######][摘除后端服务][@][tcs_drain_target()][tcs_region: {}][target_group: {:?}][target: {:?}][drain_time: {:?}]", tcs_region, target_group, target, drain_time);
+
+        let targets = [TcsClbTarget::new(target.instance_id.as_str(), target.port, None)];
+        let task_id = self.tcs_modify_target_weight(tcs_region, target_group, &targets, 0)?;
+        self.tcs_wait_clb_task(tcs_region, task_id.as_str(), waiter)?;
+
+        thread::sleep(drain_time);
+
+        let task_id = self.tcs_deregister_targets(tcs_region, target_group, &targets)?;
+        self.tcs_wait_clb_task(tcs_region, task_id.as_str(), waiter)
+    }
+
+    // 替换后端服务: 先绑定新实例并等待健康检查通过, 再摘除旧实例
+    pub fn tcs_swap_target(
+        &mut self,
+        tcs_region: &str,
+        target_group: &TcsClbTargetGroup,
+        old_target: Option<&TcsClbTarget>,
+        new_target: &TcsClbTarget,
+        drain_time: Duration,
+        waiter: &TcsWaiter,
+    ) -> Result<(), Box<dyn Error>> {
+        info!(
+            "[######][替换后端服务][@][tcs_swap_target()][tcs_region: {}][target_group: {:?}][old_target: {:?}][new_target: {:?}]",
+            tcs_region, target_group, old_target, new_target
+        );
+
+        let task_id = self.tcs_register_targets(tcs_region, target_group, std::slice::from_ref(new_target))?;
+        self.tcs_wait_clb_task(tcs_region, task_id.as_str(), waiter)?;
+        self.tcs_wait_target_healthy(tcs_region, target_group, new_target.instance_id.as_str(), waiter)?;
+
+        // 旧实例可能已被回收(已自动解绑)...
+        let Some(old_target) = old_target else { return Ok(()) };
+        let load_balancer_ids = [target_group.load_balancer_id.clone()];
+        let load_balancers = self.tcs_describe_target_health(tcs_region, &load_balancer_ids)?;
+        let registered = load_balancers.iter().flat_map(|load_balancer| load_balancer.targets(target_group)).any(|target| target.target_id == old_target.instance_id);
+        if !registered {
+            info!("[旧实例未绑定][instance_id: {}]", old_target.instance_id);
+            return Ok(());
+        }
+
+        self.tcs_drain_target(tcs_region, target_group, old_target, drain_time, waiter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::fixture;
+
+    #[test]
+    fn target_health_by_group() {
+        let load_balancer: TcsLoadBalancerHealth = fixture(json!({
+            "LoadBalancerId": "lb-1",
+            "Listeners": [
+                {
+                    "ListenerId": "lbl-http",
+                    "Protocol": "HTTP",
+                    "Port": 80,
+                    "Rules": [
+                        { "LocationId": "loc-1", "Domain": "a.com", "Url": "/", "Targets": [{ "IP": "10.0.0.1", "Port": 8080, "HealthStatus": true, "TargetId": "ins-1" }] },
+                        { "LocationId": "loc-2", "Domain": "b.com", "Url": "/", "Targets": [{ "IP": "10.0.0.2", "Port": 8080, "HealthStatus": false, "TargetId": "ins-2" }] }
+                    ]
+                },
+                {
+                    "ListenerId": "lbl-tcp",
+                    "Protocol": "TCP",
+                    "Port": 22,
+                    "Rules": [{ "Targets": [{ "IP": "10.0.0.3", "Port": 22, "HealthStatus": true, "TargetId": "ins-3" }] }]
+                }
+            ]
+        }));
+
+        let target_group = TcsClbTargetGroup {
+            load_balancer_id: "lb-1".to_string(),
+            listener_id: "lbl-http".to_string(),
+            location_id: Some("loc-2".to_string()),
+        };
+        let targets: Vec<&str> = load_balancer.targets(&target_group).iter().map(|target| target.target_id.as_str()).collect();
+        assert_eq!(targets, vec!["ins-2"]);
+        assert_eq!(target_group.payload(), json!({ "LoadBalancerId": "lb-1", "ListenerId": "lbl-http", "LocationId": "loc-2" }));
+
+        let target_group = TcsClbTargetGroup {
+            load_balancer_id: "lb-1".to_string(),
+            listener_id: "lbl-tcp".to_string(),
+            location_id: None,
+        };
+        assert_eq!(load_balancer.targets(&target_group).len(), 1);
+
+        // 四层监听器的 Rules 等字段可能为 null
+        let listener: TcsListener = fixture(json!({ "ListenerId": "lbl-tcp", "ListenerName": null, "Protocol": "TCP", "Port": 22, "Rules": null }));
+        assert!(listener.rules.is_empty());
+        let load_balancer: TcsLoadBalancer = fixture(json!({ "LoadBalancerId": "lb-1", "LoadBalancerName": "web", "LoadBalancerType": "OPEN", "LoadBalancerVips": null, "Tags": null }));
+        assert!(load_balancer.load_balancer_vips.is_empty() && load_balancer.tags.is_empty());
+    }
+}
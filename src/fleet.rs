@@ -0,0 +1,350 @@
+use log::*;
+use serde::{Deserialize, Serialize, Serializer};
+
+use std::error::Error;
+
+use crate::{TcsFilter, TcsInstanceInfo, TcsRunInstancesRequest, TcsTag, TcsWaiter, TencentCloudApi, contains_masked_value, mask_value};
+
+// 实例组标签, 用于识别实例组内的实例
+pub const TCS_FLEET_TAG_KEY: &str = "tcs-fleet";
+
+// 实例组期望状态: 保持 count 个按 name_template 命名, 配置为 instance_spec 的实例
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FleetSpec {
+    pub fleet_name: String,
+    pub tcs_region: String,
+    // 实例名称模板, {index} 替换为序号(0..count)
+    pub name_template: String,
+    pub count: usize,
+    // 序列化时隐藏登录密码, 加载后需重新设置 login_settings
+    #[serde(serialize_with = "serialize_masked")]
+    pub instance_spec: TcsRunInstancesRequest,
+    pub tags: Vec<TcsTag>,
+    // 实例组实例自动绑定的告警策略
+    #[serde(default)]
+    pub alarm_policy_id: Option<String>,
+}
+
+impl FleetSpec {
+    pub fn instance_name(&self, index: usize) -> String {
+        if self.name_template.contains("{index}") {
+            return self.name_template.replace("{index}", index.to_string().as_str());
+        }
+        format!("{}-{}", self.name_template, index)
+    }
+
+    // 实例标签(含实例组标签)
+    pub fn instance_tags(&self) -> Vec<TcsTag> {
+        let mut tags: Vec<TcsTag> = self.tags.iter().filter(|tag| tag.key != TCS_FLEET_TAG_KEY).cloned().collect();
+        tags.push(TcsTag::new(TCS_FLEET_TAG_KEY, self.fleet_name.as_str()));
+        tags
+    }
+
+    // 单个实例的创建请求
+    pub fn launch_request(&self, instance_name: &str) -> TcsRunInstancesRequest {
+        let mut request = self.instance_spec.clone();
+        request.instance_name = instance_name.to_string();
+        request.instance_count = 1;
+        request.set_instance_tags(self.instance_tags());
+        request
+    }
+
+    // 实例配置是否与期望一致, 不一致时返回原因
+    fn drift(&self, instance: &TcsInstanceInfo) -> Option<String> {
+        if TCS_FLEET_DEAD_STATES.contains(&instance.instance_state.as_str()) {
+            return Some(format!("实例状态异常({})", instance.instance_state));
+        }
+        if instance.instance_type != self.instance_spec.instance_type {
+            return Some(format!("机型不一致({} -> {})", instance.instance_type, self.instance_spec.instance_type));
+        }
+        if let Some(image_id) = &instance.image_id
+            && image_id != &self.instance_spec.image_id
+        {
+            return Some(format!("镜像不一致({} -> {})", image_id, self.instance_spec.image_id));
+        }
+        if let Some(placement) = &instance.placement
+            && placement.zone != self.instance_spec.placement.zone
+        {
+            return Some(format!("可用区不一致({} -> {})", placement.zone, self.instance_spec.placement.zone));
+        }
+        None
+    }
+}
+
+fn serialize_masked<S: Serializer>(request: &TcsRunInstancesRequest, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut value = serde_json::to_value(request).map_err(serde::ser::Error::custom)?;
+    mask_value(&mut value);
+    value.serialize(serializer)
+}
+
+// 需要重建的实例状态
+const TCS_FLEET_DEAD_STATES: [&str; 2] = ["LAUNCH_FAILED", "SHUTDOWN"];
+
+// 正在退还的实例视为不存在
+const TCS_FLEET_GONE_STATES: [&str; 1] = ["TERMINATING"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "Action")]
+pub enum FleetAction {
+    Create { instance_name: String },
+    Replace { instance_id: String, instance_name: String, reason: String },
+    Terminate { instance_id: String, instance_name: String, reason: String },
+}
+
+// 实例组变更计划
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FleetPlan {
+    pub fleet_name: String,
+    pub tcs_region: String,
+    pub actions: Vec<FleetAction>,
+}
+
+impl FleetPlan {
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FleetApplyReport {
+    pub created_instance_ids: Vec<String>,
+    pub terminated_instance_ids: Vec<String>,
+}
+
+// 对比期望状态与现有实例, 生成变更计划
+pub fn plan_fleet(spec: &FleetSpec, instance_set: &[TcsInstanceInfo]) -> FleetPlan {
+    let instance_set: Vec<&TcsInstanceInfo> = instance_set.iter().filter(|instance| !TCS_FLEET_GONE_STATES.contains(&instance.instance_state.as_str())).collect();
+    let expected_names: Vec<String> = (0..spec.count).map(|index| spec.instance_name(index)).collect();
+
+    let mut actions = vec![];
+    for instance_name in &expected_names {
+        let mut named: Vec<&&TcsInstanceInfo> = instance_set.iter().filter(|instance| &instance.instance_name == instance_name).collect();
+        // 同名实例优先保留配置一致的, 其次保留最早创建的(重建中断时保留新实例)
+        named.sort_by_key(|instance| (spec.drift(instance).is_some(), instance.created_time.clone()));
+
+        match named.split_first() {
+            None => actions.push(FleetAction::Create { instance_name: instance_name.clone() }),
+            Some((instance, duplicates)) => {
+                if let Some(reason) = spec.drift(instance) {
+                    actions.push(FleetAction::Replace {
+                        instance_id: instance.instance_id.clone(),
+                        instance_name: instance_name.clone(),
+                        reason,
+                    });
+                }
+                for duplicate in duplicates {
+                    actions.push(FleetAction::Terminate {
+                        instance_id: duplicate.instance_id.clone(),
+                        instance_name: instance_name.clone(),
+                        reason: "重复实例".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    // 超出数量或名称不匹配的实例
+    for instance in instance_set.iter().filter(|instance| !expected_names.contains(&instance.instance_name)) {
+        actions.push(FleetAction::Terminate {
+            instance_id: instance.instance_id.clone(),
+            instance_name: instance.instance_name.clone(),
+            reason: "不在期望实例列表中".to_string(),
+        });
+    }
+
+    FleetPlan {
+        fleet_name: spec.fleet_name.clone(),
+        tcs_region: spec.tcs_region.clone(),
+        actions,
+    }
+}
+
+impl TencentCloudApi {
+    // 查询实例组内的实例
+    pub fn tcs_describe_fleet_instances(&mut self, spec: &FleetSpec) -> Result<Vec<TcsInstanceInfo>, Box<dyn Error>> {
+        let tag_filter = format!("tag:{}", TCS_FLEET_TAG_KEY);
+        let filters = vec![TcsFilter::new(tag_filter.as_str(), &[spec.fleet_name.as_str()])];
+
+        self.tcs_describe_instances_by_filters(spec.tcs_region.as_str(), &filters)
+    }
+
+    // 生成实例组变更计划
+    pub fn tcs_plan_fleet(&mut self, spec: &FleetSpec) -> Result<FleetPlan, Box<dyn Error>> {
+        info!("[######][实例组变更计划][@][tcs_plan_fleet()][fleet_name: {}][count: {}]", spec.fleet_name, spec.count);
+
+        let instance_set = self.tcs_describe_fleet_instances(spec)?;
+        let plan = plan_fleet(spec, &instance_set);
+        info!("[plan: {:?}]", plan);
+
+        Ok(plan)
+    }
+
+    // 执行实例组变更计划(退还实例均经过退还保护检查), 出错时立即停止
+    // 重建实例时先创建新实例并等待运行, 再退还旧实例
+    pub fn tcs_apply_fleet_plan(&mut self, spec: &FleetSpec, plan: &FleetPlan, waiter: &TcsWaiter) -> Result<FleetApplyReport, Box<dyn Error>> {
+        info!("[######][执行实例组变更计划][@][tcs_apply_fleet_plan()][fleet_name: {}][actions: {}]", plan.fleet_name, plan.actions.len());
+
+        if plan.fleet_name != spec.fleet_name || plan.tcs_region != spec.tcs_region {
+            return Result::Err("变更计划与实例组不匹配!".into());
+        }
+        if contains_masked_value(&serde_json::to_value(&spec.instance_spec)?) {
+            return Result::Err("实例组配置中的登录密码已隐藏, 请重新设置登录设置!".into());
+        }
+
+        let tcs_region = spec.tcs_region.as_str();
+        let mut report = FleetApplyReport::default();
+        for action in &plan.actions {
+            info!("[action: {:?}]", action);
+
+            match action {
+                FleetAction::Create { instance_name } => {
+                    let instance_ids = self.tcs_run_instances_request(tcs_region, &spec.launch_request(instance_name))?;
+                    report.created_instance_ids.extend(instance_ids.iter().cloned());
+                    self.tcs_bind_fleet_alarm_policy(spec, &instance_ids)?;
+                }
+                FleetAction::Replace { instance_id, instance_name, .. } => {
+                    let instance_ids = self.tcs_run_instances_request(tcs_region, &spec.launch_request(instance_name))?;
+                    report.created_instance_ids.extend(instance_ids.iter().cloned());
+                    self.tcs_bind_fleet_alarm_policy(spec, &instance_ids)?;
+                    for new_instance_id in &instance_ids {
+                        self.tcs_wait_instance_state(tcs_region, new_instance_id.as_str(), "RUNNING", waiter)?;
+                    }
+
+                    self.tcs_terminate_instances_by_ids(tcs_region, std::slice::from_ref(instance_id))?;
+                    report.terminated_instance_ids.push(instance_id.clone());
+                }
+                FleetAction::Terminate { instance_id, .. } => {
+                    self.tcs_terminate_instances_by_ids(tcs_region, std::slice::from_ref(instance_id))?;
+                    report.terminated_instance_ids.push(instance_id.clone());
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    // 调整实例组到期望状态, 已有实例同时补绑告警策略
+    pub fn tcs_reconcile_fleet(&mut self, spec: &FleetSpec, waiter: &TcsWaiter) -> Result<FleetApplyReport, Box<dyn Error>> {
+        let plan = self.tcs_plan_fleet(spec)?;
+        let report = self.tcs_apply_fleet_plan(spec, &plan, waiter)?;
+
+        if spec.alarm_policy_id.is_some() {
+            let instance_ids: Vec<String> = self
+                .tcs_describe_fleet_instances(spec)?
+                .into_iter()
+                .filter(|instance| !TCS_FLEET_GONE_STATES.contains(&instance.instance_state.as_str()))
+                .map(|instance| instance.instance_id)
+                .filter(|instance_id| !report.created_instance_ids.contains(instance_id) && !report.terminated_instance_ids.contains(instance_id))
+                .collect();
+            self.tcs_bind_fleet_alarm_policy(spec, &instance_ids)?;
+        }
+
+        Ok(report)
+    }
+
+    // 实例绑定实例组的告警策略(未配置时跳过)
+    fn tcs_bind_fleet_alarm_policy(&mut self, spec: &FleetSpec, instance_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        if let Some(alarm_policy_id) = &spec.alarm_policy_id
+            && !instance_ids.is_empty()
+        {
+            self.tcs_bind_alarm_policy_instances(spec.tcs_region.as_str(), alarm_policy_id, instance_ids)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::{instance_info, tcs_data};
+
+    fn fleet_spec() -> FleetSpec {
+        let mut tcs_data = tcs_data("web", "web", "SPOTPAID");
+        tcs_data.instance_id = String::new();
+
+        FleetSpec {
+            fleet_name: "web".to_string(),
+            tcs_region: "ap-guangzhou".to_string(),
+            name_template: "web-{index}".to_string(),
+            count: 3,
+            instance_spec: TcsRunInstancesRequest::new(&tcs_data, "S5.MEDIUM4"),
+            tags: vec![TcsTag::new("team", "infra")],
+            alarm_policy_id: None,
+        }
+    }
+
+    fn instance(instance_id: &str, instance_name: &str, instance_type: &str, instance_state: &str) -> TcsInstanceInfo {
+        instance_info(serde_json::json!({
+            "InstanceId": instance_id,
+            "InstanceName": instance_name,
+            "InstanceType": instance_type,
+            "InstanceState": instance_state,
+            "ImageId": "img-new",
+            "Placement": { "Zone": "ap-guangzhou-3" },
+            "CreatedTime": format!("2026-01-01T00:00:0{}Z", &instance_id[4..])
+        }))
+    }
+
+    #[test]
+    fn plan_fleet_actions() {
+        let spec = fleet_spec();
+        let instance_set = vec![
+            instance("ins-1", "web-0", "S5.MEDIUM4", "RUNNING"),
+            instance("ins-2", "web-0", "S5.MEDIUM4", "RUNNING"),
+            instance("ins-3", "web-1", "S5.SMALL2", "RUNNING"),
+            instance("ins-4", "web-5", "S5.MEDIUM4", "RUNNING"),
+            instance("ins-5", "web-2", "S5.MEDIUM4", "TERMINATING"),
+        ];
+
+        let plan = plan_fleet(&spec, &instance_set);
+        assert_eq!(
+            plan.actions,
+            vec![
+                FleetAction::Terminate {
+                    instance_id: "ins-2".to_string(),
+                    instance_name: "web-0".to_string(),
+                    reason: "重复实例".to_string()
+                },
+                FleetAction::Replace {
+                    instance_id: "ins-3".to_string(),
+                    instance_name: "web-1".to_string(),
+                    reason: "机型不一致(S5.SMALL2 -> S5.MEDIUM4)".to_string()
+                },
+                FleetAction::Create { instance_name: "web-2".to_string() },
+                FleetAction::Terminate {
+                    instance_id: "ins-4".to_string(),
+                    instance_name: "web-5".to_string(),
+                    reason: "不在期望实例列表中".to_string()
+                },
+            ]
+        );
+
+        let request = spec.launch_request("web-2");
+        assert_eq!(request.instance_name, "web-2");
+        assert_eq!(request.tag_specification[0].tags, vec![TcsTag::new("team", "infra"), TcsTag::new(TCS_FLEET_TAG_KEY, "web")]);
+    }
+
+    #[test]
+    fn plan_fleet_in_sync() {
+        let spec = fleet_spec();
+        let instance_set = vec![
+            instance("ins-1", "web-0", "S5.MEDIUM4", "RUNNING"),
+            instance("ins-2", "web-1", "S5.MEDIUM4", "STOPPED"),
+            instance("ins-3", "web-2", "S5.MEDIUM4", "RUNNING"),
+        ];
+
+        assert!(plan_fleet(&spec, &instance_set).is_empty());
+    }
+
+    #[test]
+    fn fleet_spec_hides_password() {
+        let mut spec = fleet_spec();
+        spec.instance_spec.login_settings = crate::TcsLoginSettings::password("Secret-123");
+
+        let content = serde_json::to_string(&spec).unwrap();
+        assert!(!content.contains("Secret-123"));
+
+        let spec: FleetSpec = serde_json::from_str(&content).unwrap();
+        assert!(contains_masked_value(&serde_json::to_value(&spec.instance_spec).unwrap()));
+    }
+}
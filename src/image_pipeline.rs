@@ -0,0 +1,435 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::{TcsCreateImageRequest, TcsImage, TcsImageFilter, TcsWaiter, TencentCloudApi};
+
+// 镜像流水线: 关机 -> 制作镜像 -> 跨地域同步 -> 清理旧镜像
+#[derive(Debug, Clone)]
+pub struct ImagePipeline {
+    pub tcs_region: String,
+    pub instance_id: String,
+    // 镜像名称模板, 支持 {instance_id} / {date} / {timestamp}
+    pub image_name_template: String,
+    pub target_regions: Vec<String>,
+    // 每个地域保留的镜像数量(含本次镜像)
+    pub retention: usize,
+    // 制作镜像前关机, 完成后恢复运行
+    pub stop_instance: bool,
+    pub waiter: TcsWaiter,
+}
+
+// 流水线进度
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ImagePipelineStage {
+    Pending,
+    InstanceStopped,
+    ImageCreated,
+    ImageReady,
+    ImageSynced,
+    ImageRotated,
+    Done,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ImagePipelineImage {
+    pub region: String,
+    pub image_id: String,
+}
+
+// 流水线报告(同时作为断点续跑的状态文件)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ImagePipelineReport {
+    pub stage: ImagePipelineStage,
+    pub instance_id: String,
+    pub image_name: String,
+    pub image_id: Option<String>,
+    // 已提交制作镜像请求, 续跑时按名称查找镜像
+    #[serde(default)]
+    pub image_submitted: bool,
+    // 已提交同步请求的地域, 续跑时按名称查找镜像
+    #[serde(default)]
+    pub sync_submitted_regions: Vec<String>,
+    // 流水线是否关闭了实例, 需要恢复运行
+    pub instance_stopped: bool,
+    pub synced_images: Vec<ImagePipelineImage>,
+    pub deleted_images: Vec<ImagePipelineImage>,
+}
+
+impl ImagePipelineReport {
+    fn new(instance_id: &str, image_name: String) -> Self {
+        ImagePipelineReport {
+            stage: ImagePipelineStage::Pending,
+            instance_id: instance_id.to_string(),
+            image_name,
+            image_id: None,
+            image_submitted: false,
+            sync_submitted_regions: vec![],
+            instance_stopped: false,
+            synced_images: vec![],
+            deleted_images: vec![],
+        }
+    }
+}
+
+impl ImagePipeline {
+    pub fn new(tcs_region: &str, instance_id: &str, image_name_template: &str, target_regions: Vec<String>, retention: usize) -> Self {
+        ImagePipeline {
+            tcs_region: tcs_region.to_string(),
+            instance_id: instance_id.to_string(),
+            image_name_template: image_name_template.to_string(),
+            target_regions,
+            retention,
+            stop_instance: true,
+            waiter: TcsWaiter::default(),
+        }
+    }
+
+    // 生成镜像名称
+    pub fn render_image_name(&self, now: chrono::DateTime<chrono::Local>) -> String {
+        self.image_name_template
+            .replace("{instance_id}", self.instance_id.as_str())
+            .replace("{date}", now.format("%Y%m%d").to_string().as_str())
+            .replace("{timestamp}", now.timestamp().to_string().as_str())
+    }
+
+    // 同一模板(同一源实例)产生的镜像名称, 用于清理旧镜像
+    pub fn image_family(&self) -> ImageNamePattern {
+        ImageNamePattern::new(self.image_name_template.replace("{instance_id}", self.instance_id.as_str()).as_str())
+    }
+
+    // 执行流水线, 指定状态文件时可从中断处继续
+    pub fn run(&self, api: &mut TencentCloudApi, state_path: Option<&Path>) -> Result<ImagePipelineReport, Box<dyn Error>> {
+        info!("[######][镜像流水线][@][run()][instance_id: {}][image_name_template: {}][target_regions: {:?}]", self.instance_id, self.image_name_template, self.target_regions);
+
+        // 读取上次的进度...
+        let loaded_report = match state_path {
+            Some(path) if path.exists() => {
+                let report: ImagePipelineReport = serde_json::from_str(&fs::read_to_string(path)?)?;
+                if report.instance_id != self.instance_id {
+                    return Result::Err("状态文件与源实例不匹配!".into());
+                }
+                Some(report)
+            }
+            _ => None,
+        };
+        // 上次已完成时重新开始...
+        let mut report = match loaded_report {
+            Some(report) if report.stage != ImagePipelineStage::Done => {
+                info!("[继续执行][stage: {:?}][image_name: {}]", report.stage, report.image_name);
+                report
+            }
+            _ => ImagePipelineReport::new(self.instance_id.as_str(), self.render_image_name(chrono::Local::now())),
+        };
+
+        while report.stage != ImagePipelineStage::Done {
+            if let Err(err) = self.step(api, &mut report) {
+                // 出错时恢复源实例运行, 恢复失败时保留标记, 续跑时再恢复
+                self.restart_instance(api, &mut report);
+                if let Some(path) = state_path {
+                    fs::write(path, serde_json::to_string_pretty(&report)?)?;
+                }
+                return Result::Err(err);
+            }
+
+            if let Some(path) = state_path {
+                fs::write(path, serde_json::to_string_pretty(&report)?)?;
+            }
+        }
+
+        // 完成后删除状态文件, 下次运行制作新镜像
+        if let Some(path) = state_path {
+            fs::remove_file(path)?;
+        }
+
+        Ok(report)
+    }
+
+    fn restart_instance(&self, api: &mut TencentCloudApi, report: &mut ImagePipelineReport) {
+        if !report.instance_stopped {
+            return;
+        }
+
+        match api.tcs_start_instances_by_ids(self.tcs_region.as_str(), std::slice::from_ref(&self.instance_id)) {
+            Result::Ok(_) => {
+                report.instance_stopped = false;
+                // 镜像尚未提交, 续跑时重新关机
+                if report.stage == ImagePipelineStage::InstanceStopped {
+                    report.stage = ImagePipelineStage::Pending;
+                }
+            }
+            Result::Err(err) => error!("[恢复源实例失败][instance_id: {}][err: {}]", self.instance_id, err),
+        }
+    }
+
+    fn step(&self, api: &mut TencentCloudApi, report: &mut ImagePipelineReport) -> Result<(), Box<dyn Error>> {
+        info!("[镜像流水线][stage: {:?}]", report.stage);
+        let tcs_region = self.tcs_region.as_str();
+        let instance_ids = vec![self.instance_id.clone()];
+
+        match report.stage {
+            ImagePipelineStage::Pending => {
+                if self.stop_instance {
+                    let instance_set = api.tcs_describe_instances_by_ids(tcs_region, &instance_ids)?;
+                    let instance = instance_set.first().ok_or("源实例不存在!")?;
+                    if instance.instance_state != "STOPPED" {
+                        api.tcs_stop_instances_by_ids(tcs_region, &instance_ids, false)?;
+                        report.instance_stopped = true;
+                    }
+                    api.tcs_wait_instance_state(tcs_region, self.instance_id.as_str(), "STOPPED", &self.waiter)?;
+                }
+                report.stage = ImagePipelineStage::InstanceStopped;
+            }
+            ImagePipelineStage::InstanceStopped => {
+                // 镜像可能已在中断前提交, 否则同名镜像不属于本次流水线...
+                let image_id = match self.find_image(api, tcs_region, report.image_name.as_str())? {
+                    Some(image) if report.image_submitted => image.image_id,
+                    Some(image) => return Result::Err(format!("同名镜像已存在! [image_name: {}][image_id: {}]", report.image_name, image.image_id).into()),
+                    None => {
+                        let request = TcsCreateImageRequest {
+                            instance_id: self.instance_id.clone(),
+                            image_name: report.image_name.clone(),
+                            ..Default::default()
+                        };
+                        report.image_submitted = true;
+                        api.tcs_create_image(tcs_region, &request, None)?
+                    }
+                };
+                report.image_id = Some(image_id);
+                report.stage = ImagePipelineStage::ImageCreated;
+            }
+            ImagePipelineStage::ImageCreated => {
+                let image_id = report.image_id.clone().ok_or("缺少镜像ID!")?;
+                api.tcs_wait_image_state(tcs_region, image_id.as_str(), "NORMAL", &self.waiter)?;
+
+                if report.instance_stopped {
+                    api.tcs_start_instances_by_ids(tcs_region, &instance_ids)?;
+                    report.instance_stopped = false;
+                }
+                report.stage = ImagePipelineStage::ImageReady;
+            }
+            ImagePipelineStage::ImageReady => {
+                let image_id = report.image_id.clone().ok_or("缺少镜像ID!")?;
+
+                // 只同步尚未完成的地域...
+                let pending_regions: Vec<String> = self
+                    .target_regions
+                    .iter()
+                    .filter(|region| region.as_str() != tcs_region && !report.synced_images.iter().any(|image| &image.region == *region))
+                    .cloned()
+                    .collect();
+                // 中断前可能已提交同步, 目标地域已有同名镜像时不再重复同步, 未提交过的地域不允许出现同名镜像...
+                let mut sync_regions = vec![];
+                for region in &pending_regions {
+                    match self.find_image(api, region, report.image_name.as_str())? {
+                        Some(_) if report.sync_submitted_regions.contains(region) => {}
+                        Some(image) => return Result::Err(format!("目标地域已存在同名镜像! [region: {}][image_name: {}][image_id: {}]", region, report.image_name, image.image_id).into()),
+                        None => sync_regions.push(region.clone()),
+                    }
+                }
+                if !sync_regions.is_empty() {
+                    for region in &sync_regions {
+                        if !report.sync_submitted_regions.contains(region) {
+                            report.sync_submitted_regions.push(region.clone());
+                        }
+                    }
+                    api.tcs_sync_images(tcs_region, &[image_id], &sync_regions)?;
+                }
+
+                for region in pending_regions {
+                    let synced_image = self.waiter.wait(format!("{}:{}", region, report.image_name).as_str(), || self.find_image(api, region.as_str(), report.image_name.as_str()))?;
+                    api.tcs_wait_image_state(region.as_str(), synced_image.image_id.as_str(), "NORMAL", &self.waiter)?;
+
+                    report.synced_images.push(ImagePipelineImage { region, image_id: synced_image.image_id });
+                }
+                report.stage = ImagePipelineStage::ImageSynced;
+            }
+            ImagePipelineStage::ImageSynced => {
+                let mut regions = vec![self.tcs_region.clone()];
+                regions.extend(self.target_regions.iter().filter(|region| region.as_str() != tcs_region).cloned());
+
+                let family = self.image_family();
+                for region in regions {
+                    let filter = TcsImageFilter {
+                        image_type: Some("PRIVATE_IMAGE".to_string()),
+                        ..Default::default()
+                    };
+                    let image_set = api.tcs_describe_images_filter(region.as_str(), &filter)?;
+
+                    let expired_ids = select_expired_images(&image_set, &family, report.image_name.as_str(), self.retention);
+                    if expired_ids.is_empty() {
+                        continue;
+                    }
+                    api.tcs_delete_images(region.as_str(), &expired_ids)?;
+
+                    for image_id in expired_ids {
+                        report.deleted_images.push(ImagePipelineImage { region: region.clone(), image_id });
+                    }
+                }
+                report.stage = ImagePipelineStage::ImageRotated;
+            }
+            ImagePipelineStage::ImageRotated => {
+                info!("[镜像流水线完成][report: {:?}]", report);
+                report.stage = ImagePipelineStage::Done;
+            }
+            ImagePipelineStage::Done => {}
+        }
+
+        Ok(())
+    }
+
+    fn find_image(&self, api: &mut TencentCloudApi, tcs_region: &str, image_name: &str) -> Result<Option<TcsImage>, Box<dyn Error>> {
+        let filter = TcsImageFilter {
+            image_type: Some("PRIVATE_IMAGE".to_string()),
+            image_name: Some(image_name.to_string()),
+            ..Default::default()
+        };
+
+        let image_set = api.tcs_describe_images_filter(tcs_region, &filter)?;
+        Ok(image_set.into_iter().find(|image| image.image_name == image_name))
+    }
+}
+
+// 镜像名称模板片段
+#[derive(Debug, Clone, PartialEq)]
+enum ImageNamePart {
+    Literal(String),
+    // {date}: 8 位数字
+    Date,
+    // {timestamp}: 任意位数字
+    Timestamp,
+}
+
+// 镜像名称匹配规则: 模板中的占位符按其格式匹配, 其余部分必须完全一致
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageNamePattern {
+    parts: Vec<ImageNamePart>,
+}
+
+impl ImageNamePattern {
+    pub fn new(template: &str) -> Self {
+        let mut parts = vec![];
+        let mut rest = template;
+        while !rest.is_empty() {
+            let placeholder = [("{date}", ImageNamePart::Date), ("{timestamp}", ImageNamePart::Timestamp)]
+                .into_iter()
+                .filter_map(|(name, part)| rest.find(name).map(|index| (index, name.len(), part)))
+                .min_by_key(|(index, _, _)| *index);
+
+            match placeholder {
+                Some((index, length, part)) => {
+                    if index > 0 {
+                        parts.push(ImageNamePart::Literal(rest[..index].to_string()));
+                    }
+                    parts.push(part);
+                    rest = &rest[index + length..];
+                }
+                None => {
+                    parts.push(ImageNamePart::Literal(rest.to_string()));
+                    rest = "";
+                }
+            }
+        }
+        ImageNamePattern { parts }
+    }
+
+    pub fn matches(&self, image_name: &str) -> bool {
+        fn matches_parts(parts: &[ImageNamePart], name: &str) -> bool {
+            let Some((part, rest_parts)) = parts.split_first() else {
+                return name.is_empty();
+            };
+
+            match part {
+                ImageNamePart::Literal(literal) => name.strip_prefix(literal.as_str()).is_some_and(|rest| matches_parts(rest_parts, rest)),
+                ImageNamePart::Date => name.len() >= 8 && name.as_bytes()[..8].iter().all(u8::is_ascii_digit) && matches_parts(rest_parts, &name[8..]),
+                ImageNamePart::Timestamp => {
+                    let digits = name.bytes().take_while(u8::is_ascii_digit).count();
+                    (1..=digits).rev().any(|length| matches_parts(rest_parts, &name[length..]))
+                }
+            }
+        }
+
+        matches_parts(&self.parts, image_name)
+    }
+}
+
+// 按创建时间保留最新的镜像, 返回需要删除的镜像ID
+pub fn select_expired_images(image_set: &[TcsImage], family: &ImageNamePattern, current_image_name: &str, retention: usize) -> Vec<String> {
+    let mut family_images: Vec<&TcsImage> = image_set
+        .iter()
+        .filter(|image| family.matches(image.image_name.as_str()) && image.image_name != current_image_name)
+        .collect();
+    family_images.sort_by(|a, b| b.created_time.cmp(&a.created_time));
+
+    // 本次镜像占用一个保留名额
+    family_images.into_iter().skip(retention.saturating_sub(1)).map(|image| image.image_id.clone()).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::fixture;
+
+    fn image(image_id: &str, image_name: &str, created_time: &str) -> TcsImage {
+        fixture(serde_json::json!({
+            "ImageId": image_id,
+            "ImageName": image_name,
+            "ImageType": "PRIVATE_IMAGE",
+            "ImageState": "NORMAL",
+            "OsName": "CentOS 7.6 64位",
+            "Platform": "CentOS",
+            "CreatedTime": created_time
+        }))
+    }
+
+    #[test]
+    fn image_name_and_family() {
+        let pipeline = ImagePipeline::new("ap-guangzhou", "ins-1", "golden-{instance_id}-{date}", vec![], 3);
+        let family = pipeline.image_family();
+        assert!(family.matches("golden-ins-1-20260101"));
+        assert!(!family.matches("golden-ins-12-20260101"));
+        assert!(!family.matches("golden-ins-1-manual"));
+        assert!(!family.matches("golden-"));
+
+        let pipeline = ImagePipeline::new("ap-guangzhou", "ins-1", "web-{date}", vec![], 3);
+        let now = chrono::TimeZone::with_ymd_and_hms(&chrono::Local, 2026, 1, 2, 3, 4, 5).unwrap();
+        assert_eq!(pipeline.render_image_name(now), "web-20260102");
+    }
+
+    #[test]
+    fn rotate_old_images() {
+        let image_set = vec![
+            image("img-1", "web-20260101", "2026-01-01T00:00:00Z"),
+            image("img-2", "web-20260102", "2026-01-02T00:00:00Z"),
+            image("img-3", "web-20260103", "2026-01-03T00:00:00Z"),
+            image("img-4", "web-20260104", "2026-01-04T00:00:00Z"),
+            image("img-9", "other-20260101", "2026-01-01T00:00:00Z"),
+        ];
+
+        let family = ImageNamePattern::new("web-{date}");
+        let expired_ids = select_expired_images(&image_set, &family, "web-20260104", 2);
+        assert_eq!(expired_ids, vec!["img-2".to_string(), "img-1".to_string()]);
+
+        let expired_ids = select_expired_images(&image_set, &family, "web-20260104", 0);
+        assert_eq!(expired_ids.len(), 3);
+    }
+
+    #[test]
+    fn rotate_keeps_other_instance_images() {
+        // 两个实例共用同一模板
+        let image_set = vec![
+            image("img-1", "golden-ins-1-20260101", "2026-01-01T00:00:00Z"),
+            image("img-2", "golden-ins-1-20260102", "2026-01-02T00:00:00Z"),
+            image("img-3", "golden-ins-2-20260101", "2026-01-01T00:00:00Z"),
+            image("img-4", "golden-ins-12-20260101", "2026-01-01T00:00:00Z"),
+            image("img-5", "golden-base", "2025-12-01T00:00:00Z"),
+        ];
+
+        let pipeline = ImagePipeline::new("ap-guangzhou", "ins-1", "golden-{instance_id}-{date}", vec![], 1);
+        let expired_ids = select_expired_images(&image_set, &pipeline.image_family(), "golden-ins-1-20260103", pipeline.retention);
+        assert_eq!(expired_ids, vec!["img-2".to_string(), "img-1".to_string()]);
+    }
+}
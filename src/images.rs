@@ -0,0 +1,320 @@
+use log::*;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use std::error::Error;
+
+use crate::{TcsResponseEmpty, TcsWaiter, TencentCloudApi};
+
+// 镜像信息
+// [查看镜像列表](https://cloud.tencent.com/document/api/213/15715)
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsImage {
+    #[serde(rename = "ImageId")]
+    pub image_id: String,
+    #[serde(rename = "ImageName")]
+    pub image_name: String,
+    #[serde(rename = "ImageType")]
+    pub image_type: String,
+    #[serde(rename = "ImageState")]
+    pub image_state: String,
+    #[serde(rename = "ImageDescription")]
+    pub image_description: Option<String>,
+    #[serde(rename = "ImageSize")]
+    pub image_size: Option<u32>,
+    #[serde(rename = "ImageSource")]
+    pub image_source: Option<String>,
+    #[serde(rename = "ImageCreator")]
+    pub image_creator: Option<String>,
+    #[serde(rename = "OsName")]
+    pub os_name: String,
+    #[serde(rename = "Platform")]
+    pub platform: String,
+    #[serde(rename = "Architecture")]
+    pub architecture: Option<String>,
+    #[serde(rename = "CreatedTime")]
+    pub created_time: Option<String>,
+    #[serde(rename = "SyncPercent")]
+    pub sync_percent: Option<i32>,
+    #[serde(rename = "IsSupportCloudinit")]
+    pub is_support_cloudinit: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeImages {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "ImageSet")]
+    pub image_set: Vec<TcsImage>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateImage {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "ImageId")]
+    pub image_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseSyncImages {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "ImageSet")]
+    pub image_set: Option<Vec<TcsSyncImage>>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsSyncImage {
+    #[serde(rename = "ImageId")]
+    pub image_id: String,
+    #[serde(rename = "Region")]
+    pub region: String,
+}
+
+// 镜像筛选条件
+#[derive(Debug, Clone, Default)]
+pub struct TcsImageFilter {
+    pub image_ids: Vec<String>,
+    // PRIVATE_IMAGE / PUBLIC_IMAGE / SHARED_IMAGE / MARKET_IMAGE
+    pub image_type: Option<String>,
+    pub platform: Option<String>,
+    pub image_name: Option<String>,
+}
+
+impl TcsImageFilter {
+    // ImageIds 与 Filters 不能同时指定
+    fn to_payload(&self, offset: u32, limit: u32) -> Value {
+        if !self.image_ids.is_empty() {
+            return json!({
+                "ImageIds": self.image_ids,
+                "Offset": offset,
+                "Limit": limit
+            });
+        }
+
+        let mut filters = vec![];
+        if let Some(image_type) = &self.image_type {
+            filters.push(json!({ "Name": "image-type", "Values": [image_type] }));
+        }
+        if let Some(platform) = &self.platform {
+            filters.push(json!({ "Name": "platform", "Values": [platform] }));
+        }
+        if let Some(image_name) = &self.image_name {
+            filters.push(json!({ "Name": "image-name", "Values": [image_name] }));
+        }
+
+        let mut payload = json!({
+            "Offset": offset,
+            "Limit": limit
+        });
+        if !filters.is_empty() {
+            payload["Filters"] = Value::Array(filters);
+        }
+        payload
+    }
+}
+
+// 从实例创建镜像
+#[derive(Debug, Clone, Default)]
+pub struct TcsCreateImageRequest {
+    pub instance_id: String,
+    pub image_name: String,
+    pub image_description: Option<String>,
+    // 是否强制关机以制作镜像
+    pub force_poweroff: bool,
+    // 需要一并制作的数据盘
+    pub data_disk_ids: Vec<String>,
+}
+
+// 导入镜像
+#[derive(Debug, Clone, Default)]
+pub struct TcsImportImageRequest {
+    // x86_64 / i386 / arm64
+    pub architecture: String,
+    pub os_type: String,
+    pub os_version: String,
+    // COS 镜像文件地址
+    pub image_url: String,
+    pub image_name: String,
+    pub image_description: Option<String>,
+    // 跳过镜像格式检测
+    pub force: bool,
+}
+
+impl TencentCloudApi {
+    // 查看镜像列表(按条件筛选, 自动翻页)
+    pub fn tcs_describe_images_filter(&mut self, tcs_region: &str, filter: &TcsImageFilter) -> Result<Vec<TcsImage>, Box<dyn Error>> {
+        info!("[######][查看镜像列表][@][tcs_describe_images_filter()][tcs_region: {}][filter: {:?}]", tcs_region, filter);
+
+        // 配置请求参数...
+        let tcs_action = "DescribeImages";
+        let limit = 100;
+
+        let mut image_set: Vec<TcsImage> = vec![];
+        loop {
+            let payload = filter.to_payload(image_set.len() as u32, limit);
+            let tcs_response_data: TcsResponseDescribeImages = self.tcs_request_action(tcs_action, tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.image_set.len();
+            image_set.extend(tcs_response_data.image_set);
+
+            if page_count == 0 || image_set.len() >= total_count {
+                break;
+            }
+        }
+        info!("[image_set: {}]", image_set.len());
+
+        Ok(image_set)
+    }
+
+    // 查看镜像详情
+    pub fn tcs_describe_image(&mut self, tcs_region: &str, image_id: &str) -> Result<Option<TcsImage>, Box<dyn Error>> {
+        let filter = TcsImageFilter {
+            image_ids: vec![image_id.to_string()],
+            ..Default::default()
+        };
+
+        let image_set = self.tcs_describe_images_filter(tcs_region, &filter)?;
+        Ok(image_set.into_iter().find(|image| image.image_id == image_id))
+    }
+
+    // 等待镜像状态
+    pub fn tcs_wait_image_state(&mut self, tcs_region: &str, image_id: &str, image_state: &str, waiter: &TcsWaiter) -> Result<TcsImage, Box<dyn Error>> {
+        info!("[######][等待镜像状态][@][tcs_wait_image_state()][tcs_region: {}][image_id: {}][image_state: {}]", tcs_region, image_id, image_state);
+
+        let tcs_target = format!("{}:{}", image_id, image_state);
+        waiter.wait(tcs_target.as_str(), || match self.tcs_describe_image(tcs_region, image_id)? {
+            Some(image) if image.image_state == image_state => Ok(Some(image)),
+            Some(image) if image.image_state == "CREATEFAILED" || image.image_state == "IMPORTFAILED" => Err(format!("镜像制作失败({})!", image.image_state).into()),
+            _ => Ok(None),
+        })
+    }
+
+    // 创建镜像
+    // [创建镜像](https://cloud.tencent.com/document/api/213/16726)
+    pub fn tcs_create_image(&mut self, tcs_region: &str, request: &TcsCreateImageRequest, waiter: Option<&TcsWaiter>) -> Result<String, Box<dyn Error>> {
+        info!("[######][创建镜像][@][tcs_create_image()][tcs_region: {}][request: {:?}]", tcs_region, request);
+
+        // 配置请求参数...
+        let tcs_action = "CreateImage";
+
+        // 请求参数
+        let mut payload = json!({
+            "InstanceId": request.instance_id,
+            "ImageName": request.image_name,
+            "ForcePoweroff": if request.force_poweroff { "TRUE" } else { "FALSE" },
+        });
+        if let Some(image_description) = &request.image_description {
+            payload["ImageDescription"] = json!(image_description);
+        }
+        if !request.data_disk_ids.is_empty() {
+            payload["DataDiskIds"] = json!(request.data_disk_ids);
+        }
+
+        let tcs_response_data: TcsResponseCreateImage = self.tcs_request_action(tcs_action, tcs_region, &payload)?;
+        let image_id = match tcs_response_data.image_id {
+            Some(image_id) => image_id,
+            None => return Result::Err("请求成功,但无镜像ID!".into()),
+        };
+        info!("[image_id: {}]", image_id);
+
+        // 等待镜像制作完成...
+        if let Some(waiter) = waiter {
+            self.tcs_wait_image_state(tcs_region, image_id.as_str(), "NORMAL", waiter)?;
+        }
+
+        Ok(image_id)
+    }
+
+    // 删除镜像
+    pub fn tcs_delete_images(&mut self, tcs_region: &str, image_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除镜像][@][tcs_delete_images()][tcs_region: {}][image_ids: {:?}]", tcs_region, image_ids);
+
+        let payload = json!({
+            "ImageIds": image_ids
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_action("DeleteImages", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 同步镜像(跨地域复制)
+    pub fn tcs_sync_images(&mut self, tcs_region: &str, image_ids: &[String], destination_regions: &[String]) -> Result<Vec<TcsSyncImage>, Box<dyn Error>> {
+        info!("[######][同步镜像][@][tcs_sync_images()][tcs_region: {}][image_ids: {:?}][destination_regions: {:?}]", tcs_region, image_ids, destination_regions);
+
+        let payload = json!({
+            "ImageIds": image_ids,
+            "DestinationRegions": destination_regions
+        });
+
+        let tcs_response_data: TcsResponseSyncImages = self.tcs_request_action("SyncImages", tcs_region, &payload)?;
+        Ok(tcs_response_data.image_set.unwrap_or_default())
+    }
+
+    // 修改镜像分享信息
+    // permission: SHARE / CANCEL
+    pub fn tcs_modify_image_share_permission(&mut self, tcs_region: &str, image_id: &str, account_ids: &[String], permission: &str) -> Result<(), Box<dyn Error>> {
+        info!("[######][修改镜像分享信息][@][tcs_modify_image_share_permission()][tcs_region: {}][image_id: {}][account_ids: {:?}][permission: {}]", tcs_region, image_id, account_ids, permission);
+
+        let payload = json!({
+            "ImageId": image_id,
+            "AccountIds": account_ids,
+            "Permission": permission
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_action("ModifyImageSharePermission", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 外部镜像导入
+    pub fn tcs_import_image(&mut self, tcs_region: &str, request: &TcsImportImageRequest) -> Result<(), Box<dyn Error>> {
+        info!("[######][外部镜像导入][@][tcs_import_image()][tcs_region: {}][request: {:?}]", tcs_region, request);
+
+        let mut payload = json!({
+            "Architecture": request.architecture,
+            "OsType": request.os_type,
+            "OsVersion": request.os_version,
+            "ImageUrl": request.image_url,
+            "ImageName": request.image_name,
+            "Force": request.force
+        });
+        if let Some(image_description) = &request.image_description {
+            payload["ImageDescription"] = json!(image_description);
+        }
+
+        let _: TcsResponseEmpty = self.tcs_request_action("ImportImage", tcs_region, &payload)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn image_filter_payload() {
+        let filter = TcsImageFilter {
+            image_type: Some("PRIVATE_IMAGE".to_string()),
+            image_name: Some("golden".to_string()),
+            ..Default::default()
+        };
+        let payload = filter.to_payload(100, 100);
+        assert_eq!(payload["Offset"], 100);
+        assert_eq!(payload["Filters"][0]["Name"], "image-type");
+        assert_eq!(payload["Filters"][1]["Values"][0], "golden");
+
+        // 指定镜像ID时不带 Filters
+        let filter = TcsImageFilter {
+            image_ids: vec!["img-1".to_string()],
+            platform: Some("CentOS".to_string()),
+            ..Default::default()
+        };
+        let payload = filter.to_payload(0, 100);
+        assert_eq!(payload["ImageIds"][0], "img-1");
+        assert!(payload.get("Filters").is_none());
+    }
+}
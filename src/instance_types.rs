@@ -0,0 +1,272 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::{TcsFilter, TcsInstanceTypeQuota, TcsResponseZoneInstanceConfig, TencentCloudApi};
+
+// 机型配置
+// [查询实例机型列表](https://cloud.tencent.com/document/api/213/15749)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsInstanceTypeConfig {
+    #[serde(rename = "Zone")]
+    pub zone: String,
+    #[serde(rename = "InstanceType")]
+    pub instance_type: String,
+    #[serde(rename = "InstanceFamily")]
+    pub instance_family: String,
+    #[serde(rename = "GPU")]
+    pub gpu: i32,
+    #[serde(rename = "CPU")]
+    pub cpu: i32,
+    #[serde(rename = "Memory")]
+    pub memory: i32,
+    #[serde(rename = "FPGA")]
+    pub fpga: i32,
+    #[serde(rename = "GpuCount")]
+    pub gpu_count: Option<f32>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeInstanceTypeConfigs {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "InstanceTypeConfigSet")]
+    pub instance_type_config_set: Vec<TcsInstanceTypeConfig>,
+}
+
+// 机型查询条件(zones/instance_families 为空时不限)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsInstanceTypeQuery {
+    pub instance_charge_type: String,
+    pub zones: Vec<String>,
+    pub instance_families: Vec<String>,
+    pub min_cpu: i32,
+    pub min_memory: i32,
+    pub max_cpu: Option<i32>,
+    pub max_memory: Option<i32>,
+    // 精确匹配 GPU 数量, Some(0) 表示排除 GPU 机型
+    pub gpu: Option<i32>,
+    pub max_unit_price: Option<f32>,
+    // 仅返回在售机型
+    pub sell_only: bool,
+}
+
+impl TcsInstanceTypeQuery {
+    pub fn new(instance_charge_type: &str) -> Self {
+        TcsInstanceTypeQuery {
+            instance_charge_type: instance_charge_type.to_string(),
+            zones: vec![],
+            instance_families: vec![],
+            min_cpu: 0,
+            min_memory: 0,
+            max_cpu: None,
+            max_memory: None,
+            gpu: None,
+            max_unit_price: None,
+            sell_only: true,
+        }
+    }
+
+    // 服务端过滤条件
+    pub fn filters(&self) -> Vec<TcsFilter> {
+        let mut filters = vec![TcsFilter::new("instance-charge-type", &[self.instance_charge_type.as_str()])];
+        if !self.zones.is_empty() {
+            let zones: Vec<&str> = self.zones.iter().map(|zone| zone.as_str()).collect();
+            filters.push(TcsFilter::new("zone", &zones));
+        }
+        if !self.instance_families.is_empty() {
+            let instance_families: Vec<&str> = self.instance_families.iter().map(|family| family.as_str()).collect();
+            filters.push(TcsFilter::new("instance-family", &instance_families));
+        }
+        filters
+    }
+
+    pub fn matches(&self, quota: &TcsInstanceTypeQuota) -> bool {
+        quota.instance_charge_type == self.instance_charge_type
+            && (self.zones.is_empty() || self.zones.contains(&quota.zone))
+            && (self.instance_families.is_empty() || self.instance_families.contains(&quota.instance_family))
+            && quota.cpu >= self.min_cpu
+            && quota.memory >= self.min_memory
+            && self.max_cpu.is_none_or(|max_cpu| quota.cpu <= max_cpu)
+            && self.max_memory.is_none_or(|max_memory| quota.memory <= max_memory)
+            && self.gpu.is_none_or(|gpu| quota.gpu.unwrap_or(0) == gpu)
+            && self.max_unit_price.is_none_or(|max_unit_price| quota.price.unit_price_discount <= max_unit_price)
+            && (!self.sell_only || quota.status == "SELL")
+    }
+
+    // 返回全部匹配机型, 按折后单价升序(同价时 CPU/内存 小的在前)
+    pub fn select(&self, quotas: &[TcsInstanceTypeQuota]) -> Vec<TcsInstanceTypeQuota> {
+        let mut matched: Vec<TcsInstanceTypeQuota> = quotas.iter().filter(|quota| self.matches(quota)).cloned().collect();
+        matched.sort_by(compare_instance_type_quota);
+        matched
+    }
+}
+
+// 机型比较: 折后单价 > CPU > 内存 > 机型名称
+pub fn compare_instance_type_quota(a: &TcsInstanceTypeQuota, b: &TcsInstanceTypeQuota) -> Ordering {
+    a.price
+        .unit_price_discount
+        .partial_cmp(&b.price.unit_price_discount)
+        .unwrap_or(Ordering::Equal)
+        .then(a.cpu.cmp(&b.cpu))
+        .then(a.memory.cmp(&b.memory))
+        .then(a.instance_type.cmp(&b.instance_type))
+        .then(a.zone.cmp(&b.zone))
+}
+
+impl TencentCloudApi {
+    // 查询机型配置
+    pub fn tcs_describe_instance_type_configs(&mut self, tcs_region: &str, filters: &[TcsFilter]) -> Result<Vec<TcsInstanceTypeConfig>, Box<dyn Error>> {
+        info!("[######][查询机型配置][@][tcs_describe_instance_type_configs()][tcs_region: {}][filters: {:?}]", tcs_region, filters);
+
+        let payload = json!({ "Filters": filters });
+        let tcs_response_data: TcsResponseDescribeInstanceTypeConfigs = self.tcs_request_action("DescribeInstanceTypeConfigs", tcs_region, &payload)?;
+        Ok(tcs_response_data.instance_type_config_set)
+    }
+
+    // 查询可用区机型配置(含价格与售卖状态)
+    pub fn tcs_describe_instance_type_quotas(&mut self, tcs_region: &str, filters: &[TcsFilter]) -> Result<Vec<TcsInstanceTypeQuota>, Box<dyn Error>> {
+        info!("[######][查询可用区机型配置][@][tcs_describe_instance_type_quotas()][tcs_region: {}][filters: {:?}]", tcs_region, filters);
+
+        let payload = json!({ "Filters": filters });
+        let tcs_response_data: TcsResponseZoneInstanceConfig = self.tcs_request_action("DescribeZoneInstanceConfigInfos", tcs_region, &payload)?;
+        Ok(tcs_response_data.instance_type_quota_set)
+    }
+
+    // 按条件查询全部匹配机型
+    pub fn tcs_query_instance_types(&mut self, tcs_region: &str, query: &TcsInstanceTypeQuery) -> Result<Vec<TcsInstanceTypeQuota>, Box<dyn Error>> {
+        let quotas = self.tcs_describe_instance_type_quotas(tcs_region, &query.filters())?;
+        Ok(query.select(&quotas))
+    }
+}
+
+// 缓存条目(按地域与计费类型缓存全部可用区的机型)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsInstanceTypeCacheEntry {
+    pub fetched_at: i64,
+    pub quotas: Vec<TcsInstanceTypeQuota>,
+}
+
+// 机型目录: 本地缓存 DescribeZoneInstanceConfigInfos 结果, 超过 ttl 后重新查询
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsInstanceTypeCatalog {
+    pub ttl: Duration,
+    pub entries: HashMap<String, TcsInstanceTypeCacheEntry>,
+}
+
+impl TcsInstanceTypeCatalog {
+    pub fn new(ttl: Duration) -> Self {
+        TcsInstanceTypeCatalog { ttl, entries: HashMap::new() }
+    }
+
+    // 从缓存文件加载, 文件不存在时返回空目录
+    pub fn load(path: &Path, ttl: Duration) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(TcsInstanceTypeCatalog::new(ttl));
+        }
+
+        let mut catalog: TcsInstanceTypeCatalog = serde_json::from_str(&fs::read_to_string(path)?)?;
+        catalog.ttl = ttl;
+        Ok(catalog)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    fn cache_key(tcs_region: &str, instance_charge_type: &str) -> String {
+        format!("{}/{}", tcs_region, instance_charge_type)
+    }
+
+    // 缓存中未过期的机型列表
+    pub fn cached(&self, tcs_region: &str, instance_charge_type: &str, now: i64) -> Option<&[TcsInstanceTypeQuota]> {
+        let entry = self.entries.get(&TcsInstanceTypeCatalog::cache_key(tcs_region, instance_charge_type))?;
+        if now - entry.fetched_at > self.ttl.as_secs() as i64 {
+            return None;
+        }
+        Some(entry.quotas.as_slice())
+    }
+
+    pub fn insert(&mut self, tcs_region: &str, instance_charge_type: &str, quotas: Vec<TcsInstanceTypeQuota>, now: i64) {
+        let key = TcsInstanceTypeCatalog::cache_key(tcs_region, instance_charge_type);
+        self.entries.insert(key, TcsInstanceTypeCacheEntry { fetched_at: now, quotas });
+    }
+
+    // 查询全部匹配机型(优先使用缓存)
+    pub fn query(&mut self, api: &mut TencentCloudApi, tcs_region: &str, query: &TcsInstanceTypeQuery) -> Result<Vec<TcsInstanceTypeQuota>, Box<dyn Error>> {
+        let now = chrono::Local::now().timestamp();
+        let instance_charge_type = query.instance_charge_type.as_str();
+
+        if let Some(quotas) = self.cached(tcs_region, instance_charge_type, now) {
+            debug!("[机型缓存命中][tcs_region: {}][instance_charge_type: {}]", tcs_region, instance_charge_type);
+            return Ok(query.select(quotas));
+        }
+
+        let filters = [TcsFilter::new("instance-charge-type", &[instance_charge_type])];
+        let quotas = api.tcs_describe_instance_type_quotas(tcs_region, &filters)?;
+        let matched = query.select(&quotas);
+        self.insert(tcs_region, instance_charge_type, quotas, now);
+
+        Ok(matched)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::fixture;
+
+    fn quota(zone: &str, instance_type: &str, cpu: i32, memory: i32, gpu: i32, status: &str, price: f32) -> TcsInstanceTypeQuota {
+        fixture(json!({
+            "Zone": zone,
+            "InstanceType": instance_type,
+            "InstanceChargeType": "POSTPAID_BY_HOUR",
+            "Status": status,
+            "Cpu": cpu,
+            "Memory": memory,
+            "InstanceFamily": instance_type.split('.').next().unwrap(),
+            "TypeName": "",
+            "StorageBlockAmount": 0,
+            "InstanceBandwidth": 1.5,
+            "InstancePps": 30,
+            "CpuType": "",
+            "Gpu": gpu,
+            "Price": { "UnitPrice": price * 2.0, "UnitPriceDiscount": price, "Discount": 50.0, "ChargeUnit": "HOUR" }
+        }))
+    }
+
+    #[test]
+    fn select_and_cache_instance_types() {
+        let quotas = vec![
+            quota("ap-guangzhou-3", "S5.4XLARGE64", 16, 64, 0, "SELL", 2.4),
+            quota("ap-guangzhou-3", "S5.2XLARGE32", 8, 32, 0, "SELL", 1.2),
+            quota("ap-guangzhou-4", "S5.2XLARGE32", 8, 32, 0, "SOLD_OUT", 1.1),
+            quota("ap-guangzhou-4", "GN7.2XLARGE32", 8, 32, 1, "SELL", 0.9),
+            quota("ap-guangzhou-4", "S5.LARGE16", 4, 16, 0, "SELL", 0.6),
+            quota("ap-guangzhou-6", "S5.2XLARGE32", 8, 32, 0, "SELL", 1.0),
+        ];
+
+        let mut query = TcsInstanceTypeQuery::new("POSTPAID_BY_HOUR");
+        query.zones = vec!["ap-guangzhou-3".to_string(), "ap-guangzhou-4".to_string()];
+        query.min_cpu = 8;
+        query.min_memory = 32;
+        query.gpu = Some(0);
+
+        let matched: Vec<String> = query.select(&quotas).iter().map(|quota| format!("{}/{}", quota.zone, quota.instance_type)).collect();
+        assert_eq!(matched, vec!["ap-guangzhou-3/S5.2XLARGE32", "ap-guangzhou-3/S5.4XLARGE64"]);
+
+        let mut catalog = TcsInstanceTypeCatalog::new(Duration::from_secs(600));
+        catalog.insert("ap-guangzhou", "POSTPAID_BY_HOUR", quotas, 1000);
+        assert_eq!(catalog.cached("ap-guangzhou", "POSTPAID_BY_HOUR", 1600).unwrap().len(), 6);
+        assert!(catalog.cached("ap-guangzhou", "POSTPAID_BY_HOUR", 1601).is_none());
+        assert!(catalog.cached("ap-guangzhou", "SPOTPAID", 1000).is_none());
+    }
+}
@@ -0,0 +1,340 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, to_value};
+
+use std::error::Error;
+
+use crate::{TcsDisk, TcsEnhancedService, TcsFilter, TcsInstanceInfo, TcsLoginSettings, TcsResponseDescribeInstance, TcsResponseEmpty, TcsVirtualPrivateCloud, TcsWaiter, TencentCloudApi, encode_user_data, tcs_termination_guard};
+
+// 重装实例请求参数
+// [重装实例](https://cloud.tencent.com/document/api/213/15724)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsResetInstanceRequest {
+    #[serde(rename = "InstanceId")]
+    pub instance_id: String,
+    // 不指定时使用实例当前镜像
+    #[serde(rename = "ImageId", skip_serializing_if = "Option::is_none")]
+    pub image_id: Option<String>,
+    // 调整系统盘大小(只能扩容)
+    #[serde(rename = "SystemDisk", skip_serializing_if = "Option::is_none")]
+    pub system_disk: Option<TcsDisk>,
+    #[serde(rename = "LoginSettings", skip_serializing_if = "Option::is_none")]
+    pub login_settings: Option<TcsLoginSettings>,
+    #[serde(rename = "EnhancedService", skip_serializing_if = "Option::is_none")]
+    pub enhanced_service: Option<TcsEnhancedService>,
+    #[serde(rename = "HostName", skip_serializing_if = "Option::is_none")]
+    pub host_name: Option<String>,
+    // Base64 编码后的用户数据
+    #[serde(rename = "UserData", skip_serializing_if = "Option::is_none")]
+    pub user_data: Option<String>,
+}
+
+impl TcsResetInstanceRequest {
+    // 设置用户数据(自动 Base64 编码)
+    pub fn set_user_data(&mut self, user_data: &[u8]) -> Result<(), Box<dyn Error>> {
+        self.user_data = Some(encode_user_data(user_data)?);
+        Ok(())
+    }
+}
+
+// 修改实例属性(未指定的属性保持不变)
+#[derive(Debug, Clone, Default)]
+pub struct TcsModifyInstancesAttribute {
+    pub instance_name: Option<String>,
+    pub security_groups: Option<Vec<String>>,
+    pub cam_role_name: Option<String>,
+}
+
+// 扩容数据盘
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsResizeDataDisk {
+    #[serde(rename = "DiskId")]
+    pub disk_id: String,
+    #[serde(rename = "DiskSize")]
+    pub disk_size: u16,
+}
+
+impl TencentCloudApi {
+    // 按实例ID查询实例
+    pub fn tcs_describe_instances_by_ids(&mut self, tcs_region: &str, instance_ids: &[String]) -> Result<Vec<TcsInstanceInfo>, Box<dyn Error>> {
+        info!("[######][实例列表][@][tcs_describe_instances_by_ids()][tcs_region: {}][instance_ids: {:?}]", tcs_region, instance_ids);
+
+        let payload = json!({
+            "InstanceIds": instance_ids,
+            "Limit": 100
+        });
+
+        let tcs_response_data: TcsResponseDescribeInstance = self.tcs_request_action("DescribeInstances", tcs_region, &payload)?;
+        Ok(tcs_response_data.instance_set)
+    }
+
+    // 按过滤条件查询实例(自动翻页)
+    pub fn tcs_describe_instances_by_filters(&mut self, tcs_region: &str, filters: &[TcsFilter]) -> Result<Vec<TcsInstanceInfo>, Box<dyn Error>> {
+        info!("[######][实例列表][@][tcs_describe_instances_by_filters()][tcs_region: {}][filters: {:?}]", tcs_region, filters);
+
+        let limit = 100;
+        let mut instance_set: Vec<TcsInstanceInfo> = vec![];
+        loop {
+            let payload = json!({
+                "Filters": filters,
+                "Offset": instance_set.len(),
+                "Limit": limit
+            });
+
+            let tcs_response_data: TcsResponseDescribeInstance = self.tcs_request_action("DescribeInstances", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count.unwrap_or_default() as usize;
+            let page_count = tcs_response_data.instance_set.len();
+            instance_set.extend(tcs_response_data.instance_set);
+
+            if page_count == 0 || instance_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(instance_set)
+    }
+
+    // 退还实例(按实例ID), 逐个检查退还保护
+    pub fn tcs_terminate_instances_by_ids(&mut self, tcs_region: &str, instance_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!("[######][退还实例][@][tcs_terminate_instances_by_ids()][tcs_region: {}][instance_ids: {:?}]", tcs_region, instance_ids);
+
+        let instance_set = self.tcs_describe_instances_by_ids(tcs_region, instance_ids)?;
+        for instance_id in instance_ids {
+            match instance_set.iter().find(|instance| &instance.instance_id == instance_id) {
+                Some(instance) => tcs_termination_guard(instance)?,
+                None => return Result::Err(format!("退还失败:实例不存在({})!", instance_id).into()),
+            }
+        }
+
+        let payload = json!({
+            "InstanceIds": instance_ids
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_action("TerminateInstances", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 等待实例满足条件, ready 返回 Err(..) 时立即结束
+    pub fn tcs_wait_instance<F>(&mut self, tcs_region: &str, instance_id: &str, tcs_target: &str, waiter: &TcsWaiter, mut ready: F) -> Result<TcsInstanceInfo, Box<dyn Error>>
+    where
+        F: FnMut(&TcsInstanceInfo) -> Result<bool, Box<dyn Error>>,
+    {
+        let instance_ids = vec![instance_id.to_string()];
+        waiter.wait(tcs_target, || {
+            let instance_set = self.tcs_describe_instances_by_ids(tcs_region, &instance_ids)?;
+            match instance_set.into_iter().next() {
+                Some(instance) if ready(&instance)? => Ok(Some(instance)),
+                _ => Ok(None),
+            }
+        })
+    }
+
+    // 等待实例状态
+    pub fn tcs_wait_instance_state(&mut self, tcs_region: &str, instance_id: &str, instance_state: &str, waiter: &TcsWaiter) -> Result<TcsInstanceInfo, Box<dyn Error>> {
+        info!("[######][等待实例状态][@][tcs_wait_instance_state()][tcs_region: {}][instance_id: {}][instance_state: {}]", tcs_region, instance_id, instance_state);
+
+        let tcs_target = format!("{}:{}", instance_id, instance_state);
+        self.tcs_wait_instance(tcs_region, instance_id, tcs_target.as_str(), waiter, |instance| {
+            if instance.instance_state == "LAUNCH_FAILED" {
+                return Err("实例创建失败(LAUNCH_FAILED)!".into());
+            }
+            Ok(instance.instance_state == instance_state)
+        })
+    }
+
+    // 等待实例操作完成(按操作请求ID匹配 LatestOperationRequestId)
+    pub fn tcs_wait_instance_operation(&mut self, tcs_region: &str, instance_id: &str, request_id: &str, waiter: &TcsWaiter) -> Result<TcsInstanceInfo, Box<dyn Error>> {
+        info!("[######][等待实例操作][@][tcs_wait_instance_operation()][tcs_region: {}][instance_id: {}][request_id: {}]", tcs_region, instance_id, request_id);
+
+        let tcs_target = format!("{}:{}", instance_id, request_id);
+        self.tcs_wait_instance(tcs_region, instance_id, tcs_target.as_str(), waiter, |instance| {
+            if instance.latest_operation_request_id.as_deref() != Some(request_id) {
+                return Ok(false);
+            }
+            match instance.latest_operation_state.as_deref() {
+                Some("SUCCESS") => Ok(true),
+                Some("FAILED") => Err(format!("实例操作失败({})!", instance.latest_operation.clone().unwrap_or_default()).into()),
+                _ => Ok(false),
+            }
+        })
+    }
+
+    // 批量等待实例操作完成
+    pub(crate) fn tcs_wait_instances_operation(&mut self, tcs_region: &str, instance_ids: &[String], request_id: &str, waiter: Option<&TcsWaiter>) -> Result<(), Box<dyn Error>> {
+        if let Some(waiter) = waiter {
+            for instance_id in instance_ids {
+                self.tcs_wait_instance_operation(tcs_region, instance_id.as_str(), request_id, waiter)?;
+            }
+        }
+        Ok(())
+    }
+
+    // 重装实例(指定请求参数), 指定 waiter 时等待重装操作完成
+    pub fn tcs_reset_instance_request(&mut self, tcs_region: &str, request: &TcsResetInstanceRequest, waiter: Option<&TcsWaiter>) -> Result<(), Box<dyn Error>> {
+        info!("[######][重装实例][@][tcs_reset_instance_request()][tcs_region: {}][request: {:?}]", tcs_region, request);
+
+        let payload = to_value(request)?;
+
+        let tcs_response_data: TcsResponseEmpty = self.tcs_request_action("ResetInstance", tcs_region, &payload)?;
+
+        // 重装刚提交时实例仍为 RUNNING, 按本次请求ID等待操作结果...
+        self.tcs_wait_instances_operation(tcs_region, std::slice::from_ref(&request.instance_id), tcs_response_data.request_id.as_str(), waiter)
+    }
+
+    // 调整实例配置
+    // [调整实例配置](https://cloud.tencent.com/document/api/213/15744)
+    pub fn tcs_reset_instances_type(&mut self, tcs_region: &str, instance_ids: &[String], instance_type: &str, force_stop: bool, waiter: Option<&TcsWaiter>) -> Result<(), Box<dyn Error>> {
+        info!("[######][调整实例配置][@][tcs_reset_instances_type()][tcs_region: {}][instance_ids: {:?}][instance_type: {}]", tcs_region, instance_ids, instance_type);
+
+        let payload = json!({
+            "InstanceIds": instance_ids,
+            "InstanceType": instance_type,
+            "ForceStop": force_stop
+        });
+
+        let tcs_response_data: TcsResponseEmpty = self.tcs_request_action("ResetInstancesType", tcs_region, &payload)?;
+        self.tcs_wait_instances_operation(tcs_region, instance_ids, tcs_response_data.request_id.as_str(), waiter)
+    }
+
+    // 修改实例属性(名称 / 安全组 / CAM 角色)
+    pub fn tcs_modify_instances_attribute(&mut self, tcs_region: &str, instance_ids: &[String], attribute: &TcsModifyInstancesAttribute) -> Result<(), Box<dyn Error>> {
+        info!("[######][修改实例属性][@][tcs_modify_instances_attribute()][tcs_region: {}][instance_ids: {:?}][attribute: {:?}]", tcs_region, instance_ids, attribute);
+
+        let mut payload = json!({
+            "InstanceIds": instance_ids
+        });
+        if let Some(instance_name) = &attribute.instance_name {
+            payload["InstanceName"] = json!(instance_name);
+        }
+        if let Some(security_groups) = &attribute.security_groups {
+            payload["SecurityGroups"] = json!(security_groups);
+        }
+        if let Some(cam_role_name) = &attribute.cam_role_name {
+            payload["CamRoleName"] = json!(cam_role_name);
+        }
+
+        let _: TcsResponseEmpty = self.tcs_request_action("ModifyInstancesAttribute", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 修改实例所属项目
+    pub fn tcs_modify_instances_project(&mut self, tcs_region: &str, instance_ids: &[String], project_id: i64) -> Result<(), Box<dyn Error>> {
+        info!("[######][修改实例所属项目][@][tcs_modify_instances_project()][tcs_region: {}][instance_ids: {:?}][project_id: {}]", tcs_region, instance_ids, project_id);
+
+        let payload = json!({
+            "InstanceIds": instance_ids,
+            "ProjectId": project_id
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_action("ModifyInstancesProject", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 修改实例私有网络
+    pub fn tcs_modify_instances_vpc_attribute(
+        &mut self,
+        tcs_region: &str,
+        instance_ids: &[String],
+        virtual_private_cloud: &TcsVirtualPrivateCloud,
+        force_stop: bool,
+        reserve_host_name: bool,
+        waiter: Option<&TcsWaiter>,
+    ) -> Result<(), Box<dyn Error>> {
+        info!(
+            "[######][修改实例私有网络][@][tcs_modify_instances_vpc_attribute()][tcs_region: {}][instance_ids: {:?}][virtual_private_cloud: {:?}]",
+            tcs_region, instance_ids, virtual_private_cloud
+        );
+
+        let payload = json!({
+            "InstanceIds": instance_ids,
+            "VirtualPrivateCloud": virtual_private_cloud,
+            "ForceStop": force_stop,
+            "ReserveHostName": reserve_host_name
+        });
+
+        let tcs_response_data: TcsResponseEmpty = self.tcs_request_action("ModifyInstancesVpcAttribute", tcs_region, &payload)?;
+        self.tcs_wait_instances_operation(tcs_region, instance_ids, tcs_response_data.request_id.as_str(), waiter)
+    }
+
+    // 扩容实例磁盘
+    pub fn tcs_resize_instance_disks(&mut self, tcs_region: &str, instance_id: &str, data_disks: &[TcsResizeDataDisk], force_stop: bool, waiter: Option<&TcsWaiter>) -> Result<(), Box<dyn Error>> {
+        info!("[######][扩容实例磁盘][@][tcs_resize_instance_disks()][tcs_region: {}][instance_id: {}][data_disks: {:?}]", tcs_region, instance_id, data_disks);
+
+        let payload = json!({
+            "InstanceId": instance_id,
+            "DataDisks": data_disks,
+            "ForceStop": force_stop
+        });
+
+        let tcs_response_data: TcsResponseEmpty = self.tcs_request_action("ResizeInstanceDisks", tcs_region, &payload)?;
+        self.tcs_wait_instances_operation(tcs_region, &[instance_id.to_string()], tcs_response_data.request_id.as_str(), waiter)
+    }
+
+    // 重置实例密码
+    pub fn tcs_reset_instances_password(&mut self, tcs_region: &str, instance_ids: &[String], password: &str, user_name: Option<&str>, force_stop: bool, waiter: Option<&TcsWaiter>) -> Result<(), Box<dyn Error>> {
+        info!("[######][重置实例密码][@][tcs_reset_instances_password()][tcs_region: {}][instance_ids: {:?}][user_name: {:?}]", tcs_region, instance_ids, user_name);
+
+        let mut payload = json!({
+            "InstanceIds": instance_ids,
+            "Password": password,
+            "ForceStop": force_stop
+        });
+        if let Some(user_name) = user_name {
+            payload["UserName"] = json!(user_name);
+        }
+
+        let tcs_response_data: TcsResponseEmpty = self.tcs_request_action("ResetInstancesPassword", tcs_region, &payload)?;
+        self.tcs_wait_instances_operation(tcs_region, instance_ids, tcs_response_data.request_id.as_str(), waiter)
+    }
+
+    // 启动实例(按实例ID)
+    pub fn tcs_start_instances_by_ids(&mut self, tcs_region: &str, instance_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        info!("[######][启动实例][@][tcs_start_instances_by_ids()][tcs_region: {}][instance_ids: {:?}]", tcs_region, instance_ids);
+
+        let payload = json!({
+            "InstanceIds": instance_ids
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_action("StartInstances", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 关闭实例(按实例ID)
+    pub fn tcs_stop_instances_by_ids(&mut self, tcs_region: &str, instance_ids: &[String], force_stop: bool) -> Result<(), Box<dyn Error>> {
+        info!("[######][关闭实例][@][tcs_stop_instances_by_ids()][tcs_region: {}][instance_ids: {:?}][force_stop: {}]", tcs_region, instance_ids, force_stop);
+
+        let payload = json!({
+            "InstanceIds": instance_ids,
+            "ForceStop": force_stop
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_action("StopInstances", tcs_region, &payload)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reset_instance_payload() {
+        let request = TcsResetInstanceRequest {
+            instance_id: "ins-1".to_string(),
+            system_disk: Some(TcsDisk::new("CLOUD_PREMIUM", 100)),
+            login_settings: Some(TcsLoginSettings::keep_image_login()),
+            ..Default::default()
+        };
+
+        let payload = to_value(&request).unwrap();
+        assert_eq!(
+            payload,
+            json!({
+                "InstanceId": "ins-1",
+                "SystemDisk": { "DiskType": "CLOUD_PREMIUM", "DiskSize": 100 },
+                "LoginSettings": { "KeepImageLogin": "TRUE" }
+            })
+        );
+    }
+}
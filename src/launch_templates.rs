@@ -0,0 +1,291 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json, to_value};
+
+use std::error::Error;
+
+use crate::{TcsDisk, TcsInstanceMarketOptions, TcsInternetAccessible, TcsLoginSettings, TcsPlacement, TcsResponseEmpty, TcsResponseRunInstances, TcsRunInstancesRequest, TcsVirtualPrivateCloud, TencentCloudApi};
+
+// 实例启动模板引用
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsLaunchTemplate {
+    #[serde(rename = "LaunchTemplateId")]
+    pub launch_template_id: String,
+    // 不指定时使用默认版本
+    #[serde(rename = "LaunchTemplateVersion", skip_serializing_if = "Option::is_none")]
+    pub launch_template_version: Option<u64>,
+}
+
+// 使用启动模板创建实例时覆盖的参数(未指定的参数取自模板)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsRunInstancesOverrides {
+    #[serde(rename = "Placement", skip_serializing_if = "Option::is_none")]
+    pub placement: Option<TcsPlacement>,
+    #[serde(rename = "InstanceChargeType", skip_serializing_if = "Option::is_none")]
+    pub instance_charge_type: Option<String>,
+    #[serde(rename = "InstanceType", skip_serializing_if = "Option::is_none")]
+    pub instance_type: Option<String>,
+    #[serde(rename = "ImageId", skip_serializing_if = "Option::is_none")]
+    pub image_id: Option<String>,
+    #[serde(rename = "SystemDisk", skip_serializing_if = "Option::is_none")]
+    pub system_disk: Option<TcsDisk>,
+    #[serde(rename = "DataDisks", skip_serializing_if = "Option::is_none")]
+    pub data_disks: Option<Vec<TcsDisk>>,
+    #[serde(rename = "InternetAccessible", skip_serializing_if = "Option::is_none")]
+    pub internet_accessible: Option<TcsInternetAccessible>,
+    #[serde(rename = "VirtualPrivateCloud", skip_serializing_if = "Option::is_none")]
+    pub virtual_private_cloud: Option<TcsVirtualPrivateCloud>,
+    #[serde(rename = "SecurityGroupIds", skip_serializing_if = "Option::is_none")]
+    pub security_group_ids: Option<Vec<String>>,
+    #[serde(rename = "InstanceName", skip_serializing_if = "Option::is_none")]
+    pub instance_name: Option<String>,
+    #[serde(rename = "InstanceMarketOptions", skip_serializing_if = "Option::is_none")]
+    pub instance_market_options: Option<TcsInstanceMarketOptions>,
+    #[serde(rename = "LoginSettings", skip_serializing_if = "Option::is_none")]
+    pub login_settings: Option<TcsLoginSettings>,
+    #[serde(rename = "InstanceCount", skip_serializing_if = "Option::is_none")]
+    pub instance_count: Option<u32>,
+    #[serde(rename = "UserData", skip_serializing_if = "Option::is_none")]
+    pub user_data: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsLaunchTemplateInfo {
+    #[serde(rename = "LaunchTemplateId")]
+    pub launch_template_id: String,
+    #[serde(rename = "LaunchTemplateName")]
+    pub launch_template_name: String,
+    #[serde(rename = "DefaultVersion")]
+    pub default_version: Option<u64>,
+    #[serde(rename = "LatestVersionNumber")]
+    pub latest_version_number: Option<u64>,
+    #[serde(rename = "CreationTime")]
+    pub creation_time: Option<String>,
+    #[serde(rename = "CreatedBy")]
+    pub created_by: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsLaunchTemplateVersionInfo {
+    #[serde(rename = "LaunchTemplateId")]
+    pub launch_template_id: String,
+    #[serde(rename = "LaunchTemplateVersion")]
+    pub launch_template_version: u64,
+    #[serde(rename = "LaunchTemplateVersionDescription")]
+    pub launch_template_version_description: Option<String>,
+    #[serde(rename = "IsDefaultVersion")]
+    pub is_default_version: bool,
+    // 模板内容(与 RunInstances 参数一致)
+    #[serde(rename = "LaunchTemplateVersionData")]
+    pub launch_template_version_data: Value,
+    #[serde(rename = "CreationTime")]
+    pub creation_time: Option<String>,
+    #[serde(rename = "CreatedBy")]
+    pub created_by: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateLaunchTemplate {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "LaunchTemplateId")]
+    pub launch_template_id: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateLaunchTemplateVersion {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "LaunchTemplateVersionNumber")]
+    pub launch_template_version_number: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeLaunchTemplates {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "LaunchTemplateSet")]
+    pub launch_template_set: Vec<TcsLaunchTemplateInfo>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeLaunchTemplateVersions {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "LaunchTemplateVersionSet")]
+    pub launch_template_version_set: Vec<TcsLaunchTemplateVersionInfo>,
+}
+
+impl TencentCloudApi {
+    // 创建实例启动模板(模板内容沿用创建实例请求参数)
+    // [创建实例启动模板](https://cloud.tencent.com/document/api/213/66326)
+    pub fn tcs_create_launch_template(&mut self, tcs_region: &str, launch_template_name: &str, description: Option<&str>, request: &TcsRunInstancesRequest) -> Result<String, Box<dyn Error>> {
+        info!("[######][创建实例启动模板][@][tcs_create_launch_template()][tcs_region: {}][launch_template_name: {}]", tcs_region, launch_template_name);
+
+        let mut payload = to_value(request)?;
+        payload["LaunchTemplateName"] = json!(launch_template_name);
+        if let Some(description) = description {
+            payload["LaunchTemplateVersionDescription"] = json!(description);
+        }
+
+        let tcs_response_data: TcsResponseCreateLaunchTemplate = self.tcs_request_action("CreateLaunchTemplate", tcs_region, &payload)?;
+        Ok(tcs_response_data.launch_template_id)
+    }
+
+    // 创建实例启动模板版本, 返回新版本号
+    pub fn tcs_create_launch_template_version(&mut self, tcs_region: &str, launch_template_id: &str, description: Option<&str>, request: &TcsRunInstancesRequest) -> Result<u64, Box<dyn Error>> {
+        info!("[######][创建实例启动模板版本][@][tcs_create_launch_template_version()][tcs_region: {}][launch_template_id: {}]", tcs_region, launch_template_id);
+
+        let mut payload = to_value(request)?;
+        payload["LaunchTemplateId"] = json!(launch_template_id);
+        if let Some(description) = description {
+            payload["LaunchTemplateVersionDescription"] = json!(description);
+        }
+
+        let tcs_response_data: TcsResponseCreateLaunchTemplateVersion = self.tcs_request_action("CreateLaunchTemplateVersion", tcs_region, &payload)?;
+        Ok(tcs_response_data.launch_template_version_number)
+    }
+
+    // 查询实例启动模板
+    pub fn tcs_describe_launch_templates(&mut self, tcs_region: &str, launch_template_ids: &[String], launch_template_name: Option<&str>) -> Result<Vec<TcsLaunchTemplateInfo>, Box<dyn Error>> {
+        info!(
+            "[######][查询实例启动模板][@][tcs_describe_launch_templates()][tcs_region: {}][launch_template_ids: {:?}][launch_template_name: {:?}]",
+            tcs_region, launch_template_ids, launch_template_name
+        );
+
+        // 模板 ID 列表与 Filters 不能同时指定
+        if !launch_template_ids.is_empty() && launch_template_name.is_some() {
+            return Result::Err("不能同时按模板ID与模板名称查询!".into());
+        }
+
+        let limit = 100;
+        let mut launch_template_set: Vec<TcsLaunchTemplateInfo> = vec![];
+        loop {
+            let mut payload = json!({
+                "Offset": launch_template_set.len(),
+                "Limit": limit
+            });
+            if !launch_template_ids.is_empty() {
+                payload["LaunchTemplateIds"] = json!(launch_template_ids);
+            } else if let Some(launch_template_name) = launch_template_name {
+                payload["Filters"] = json!([{ "Name": "launch-template-name", "Values": [launch_template_name] }]);
+            }
+
+            let tcs_response_data: TcsResponseDescribeLaunchTemplates = self.tcs_request_action("DescribeLaunchTemplates", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.launch_template_set.len();
+            launch_template_set.extend(tcs_response_data.launch_template_set);
+
+            if page_count == 0 || launch_template_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(launch_template_set)
+    }
+
+    // 查询实例启动模板版本
+    pub fn tcs_describe_launch_template_versions(&mut self, tcs_region: &str, launch_template_id: &str, versions: &[u64]) -> Result<Vec<TcsLaunchTemplateVersionInfo>, Box<dyn Error>> {
+        info!("[######][查询实例启动模板版本][@][tcs_describe_launch_template_versions()][tcs_region: {}][launch_template_id: {}][versions: {:?}]", tcs_region, launch_template_id, versions);
+
+        let limit = 100;
+        let mut launch_template_version_set: Vec<TcsLaunchTemplateVersionInfo> = vec![];
+        loop {
+            let mut payload = json!({
+                "LaunchTemplateId": launch_template_id,
+                "Offset": launch_template_version_set.len(),
+                "Limit": limit
+            });
+            if !versions.is_empty() {
+                payload["LaunchTemplateVersions"] = json!(versions);
+            }
+
+            let tcs_response_data: TcsResponseDescribeLaunchTemplateVersions = self.tcs_request_action("DescribeLaunchTemplateVersions", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.launch_template_version_set.len();
+            launch_template_version_set.extend(tcs_response_data.launch_template_version_set);
+
+            if page_count == 0 || launch_template_version_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(launch_template_version_set)
+    }
+
+    // 修改实例启动模板默认版本
+    pub fn tcs_modify_launch_template_default_version(&mut self, tcs_region: &str, launch_template_id: &str, default_version: u64) -> Result<(), Box<dyn Error>> {
+        info!("[######][修改实例启动模板默认版本][@][tcs_modify_launch_template_default_version()][tcs_region: {}][launch_template_id: {}][default_version: {}]", tcs_region, launch_template_id, default_version);
+
+        let payload = json!({
+            "LaunchTemplateId": launch_template_id,
+            "DefaultVersion": default_version
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_action("ModifyLaunchTemplateDefaultVersion", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 删除实例启动模板
+    pub fn tcs_delete_launch_template(&mut self, tcs_region: &str, launch_template_id: &str) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除实例启动模板][@][tcs_delete_launch_template()][tcs_region: {}][launch_template_id: {}]", tcs_region, launch_template_id);
+
+        let payload = json!({
+            "LaunchTemplateId": launch_template_id
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_action("DeleteLaunchTemplate", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 使用启动模板创建实例
+    pub fn tcs_run_instances_from_template(&mut self, tcs_region: &str, launch_template: &TcsLaunchTemplate, overrides: &TcsRunInstancesOverrides) -> Result<Vec<String>, Box<dyn Error>> {
+        info!("[######][创建实例][@][tcs_run_instances_from_template()][tcs_region: {}][launch_template: {:?}]", tcs_region, launch_template);
+
+        let payload = tcs_run_from_template_payload(launch_template, overrides)?;
+
+        let tcs_response_data: TcsResponseRunInstances = self.tcs_request_action("RunInstances", tcs_region, &payload)?;
+        Ok(tcs_response_data.instance_id_set)
+    }
+}
+
+// 使用启动模板创建实例的请求参数: 覆盖参数 + 模板
+pub fn tcs_run_from_template_payload(launch_template: &TcsLaunchTemplate, overrides: &TcsRunInstancesOverrides) -> Result<Value, Box<dyn Error>> {
+    let mut payload = to_value(overrides)?;
+    payload["LaunchTemplate"] = to_value(launch_template)?;
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn run_from_template_payload() {
+        let launch_template = TcsLaunchTemplate {
+            launch_template_id: "lt-1".to_string(),
+            launch_template_version: None,
+        };
+        let overrides = TcsRunInstancesOverrides {
+            instance_name: Some("web-0".to_string()),
+            instance_count: Some(2),
+            ..Default::default()
+        };
+
+        let payload = tcs_run_from_template_payload(&launch_template, &overrides).unwrap();
+        assert_eq!(
+            payload,
+            json!({
+                "InstanceName": "web-0",
+                "InstanceCount": 2,
+                "LaunchTemplate": { "LaunchTemplateId": "lt-1" }
+            })
+        );
+    }
+}
@@ -2,7 +2,8 @@
 // use deflate::{deflate_bytes_zlib_conf, Compression};
 use log::*;
 use ring::hmac;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde::de::{DeserializeOwned, Deserializer};
 use serde_json::{Value, json, to_string};
 // use std::collections::HashMap;
 
@@ -13,6 +14,60 @@ use reqwest::header::HeaderMap;
 use std::error::Error;
 use std::io::Read;
 
+mod addresses;
+mod alarms;
+mod backup;
+mod billing;
+mod cbs;
+mod charge;
+mod clb;
+mod fleet;
+mod image_pipeline;
+mod images;
+mod instance_types;
+mod instances;
+mod key_pairs;
+mod launch_templates;
+mod metadata;
+mod monitor;
+mod plan;
+mod regions;
+mod run_instances;
+mod security_groups;
+mod spot;
+mod tat;
+#[cfg(test)]
+mod test_support;
+mod user_data;
+mod vpc;
+mod waiter;
+
+pub use addresses::*;
+pub use alarms::*;
+pub use backup::*;
+pub use billing::*;
+pub use cbs::*;
+pub use charge::*;
+pub use clb::*;
+pub use fleet::*;
+pub use image_pipeline::*;
+pub use images::*;
+pub use instance_types::*;
+pub use instances::*;
+pub use key_pairs::*;
+pub use launch_templates::*;
+pub use metadata::*;
+pub use monitor::*;
+pub use plan::*;
+pub use regions::*;
+pub use run_instances::*;
+pub use security_groups::*;
+pub use spot::*;
+pub use tat::*;
+pub use user_data::*;
+pub use vpc::*;
+pub use waiter::*;
+
 // 打印请求日志数据...
 fn load_response(response: &mut reqwest::blocking::Response) -> Result<String, Box<dyn Error>> {
     // 读取到字符串
@@ -20,7 +75,7 @@ fn load_response(response: &mut reqwest::blocking::Response) -> Result<String, B
     response.read_to_string(&mut content)?;
 
     trace!("[TCS-API-RESPONSE-INFO]: \n{:?}", response);
-    trace!("[TCS-API-RESPONSE-CONTENT]: \n{}", content);
+    trace!("[TCS-API-RESPONSE-CONTENT]: \n{}", mask_payload(&content));
 
     // 转化成 Value ...
     //let value: Value = from_str(&content)?;
@@ -36,7 +91,7 @@ fn load_response(response: &mut reqwest::blocking::Response) -> Result<String, B
     Ok(content)
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Clone)]
 pub struct TcsData {
     pub tcs_title: String,
     pub tcs_region: String,
@@ -50,6 +105,24 @@ pub struct TcsData {
     pub tcs_info: TcsInfo,
 }
 
+// 日志中隐藏登录密码
+impl std::fmt::Debug for TcsData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcsData")
+            .field("tcs_title", &self.tcs_title)
+            .field("tcs_region", &self.tcs_region)
+            .field("tcs_zone", &self.tcs_zone)
+            .field("host_name", &self.host_name)
+            .field("instance_name", &self.instance_name)
+            .field("instance_id", &self.instance_id)
+            .field("tcs_image_id", &self.tcs_image_id)
+            .field("password", &"******")
+            .field("key_ids", &self.key_ids)
+            .field("tcs_info", &self.tcs_info)
+            .finish()
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct TcsInfo {
     pub instance_charge_type: String,
@@ -64,6 +137,13 @@ pub struct TcsResponse {
     pub response: Value,
 }
 
+// 无返回数据的接口响应
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseEmpty {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct TcsResponseError {
     #[serde(rename = "RequestId")]
@@ -80,6 +160,15 @@ pub struct TcsApiError {
     pub message: String,
 }
 
+// 接口可能返回 null 的列表字段, 与缺省字段一样按空值处理
+pub(crate) fn null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct TcsResponseZoneInstanceConfig {
     #[serde(rename = "RequestId")]
@@ -88,7 +177,7 @@ pub struct TcsResponseZoneInstanceConfig {
     pub instance_type_quota_set: Vec<TcsInstanceTypeQuota>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TcsInstanceTypeQuota {
     #[serde(rename = "Zone")]
     pub zone: String,
@@ -107,18 +196,24 @@ pub struct TcsInstanceTypeQuota {
     #[serde(rename = "TypeName")]
     pub type_name: String,
     #[serde(rename = "StorageBlockAmount")]
-    pub storage_block_amount: u8,
+    pub storage_block_amount: u32,
     #[serde(rename = "InstanceBandwidth")]
     pub instance_bandwidth: f32,
     #[serde(rename = "InstancePps")]
-    pub instance_pps: u8,
+    pub instance_pps: u32,
     #[serde(rename = "CpuType")]
     pub cpu_type: String,
+    #[serde(rename = "Gpu")]
+    pub gpu: Option<i32>,
+    #[serde(rename = "Fpga")]
+    pub fpga: Option<i32>,
+    #[serde(rename = "GpuCount")]
+    pub gpu_count: Option<f32>,
     #[serde(rename = "Price")]
     pub price: TcsInstanceTypeQuotaPrice,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TcsInstanceTypeQuotaPrice {
     #[serde(rename = "UnitPrice")]
     pub unit_price: f32,
@@ -152,6 +247,8 @@ pub struct TcsInstanceStatus {
 pub struct TcsResponseDescribeInstance {
     #[serde(rename = "RequestId")]
     pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: Option<u32>,
     #[serde(rename = "InstanceSet")]
     pub instance_set: Vec<TcsInstanceInfo>,
 }
@@ -186,6 +283,130 @@ pub struct TcsInstanceInfo {
     pub private_ip_addresses: Option<Vec<String>>,
     #[serde(rename = "PublicIpAddresses")]
     pub public_ip_addresses: Option<Vec<String>>,
+    #[serde(rename = "SecurityGroupIds")]
+    pub security_group_ids: Option<Vec<String>>,
+    #[serde(rename = "VirtualPrivateCloud")]
+    pub virtual_private_cloud: Option<TcsVirtualPrivateCloud>,
+    #[serde(rename = "LatestOperation")]
+    pub latest_operation: Option<String>,
+    #[serde(rename = "LatestOperationState")]
+    pub latest_operation_state: Option<String>,
+    #[serde(rename = "LatestOperationRequestId")]
+    pub latest_operation_request_id: Option<String>,
+    #[serde(rename = "RenewFlag")]
+    pub renew_flag: Option<String>,
+    #[serde(rename = "ExpiredTime")]
+    pub expired_time: Option<String>,
+    #[serde(rename = "CreatedTime")]
+    pub created_time: Option<String>,
+    #[serde(rename = "Placement")]
+    pub placement: Option<TcsPlacement>,
+    #[serde(rename = "Tags")]
+    pub tags: Option<Vec<TcsTag>>,
+}
+
+// 云产品接口: 域名/服务名/版本
+#[derive(Debug, Clone, Copy)]
+pub struct TcsService {
+    pub host: &'static str,
+    pub service: &'static str,
+    pub version: &'static str,
+}
+
+pub const TCS_SERVICE_CVM: TcsService = TcsService {
+    host: "cvm.tencentcloudapi.com",
+    service: "cvm",
+    version: "2017-03-12",
+};
+
+pub const TCS_SERVICE_VPC: TcsService = TcsService {
+    host: "vpc.tencentcloudapi.com",
+    service: "vpc",
+    version: "2017-03-12",
+};
+
+pub const TCS_SERVICE_CBS: TcsService = TcsService {
+    host: "cbs.tencentcloudapi.com",
+    service: "cbs",
+    version: "2017-03-12",
+};
+
+pub const TCS_SERVICE_CLB: TcsService = TcsService {
+    host: "clb.tencentcloudapi.com",
+    service: "clb",
+    version: "2018-03-17",
+};
+
+pub const TCS_SERVICE_TAT: TcsService = TcsService {
+    host: "tat.tencentcloudapi.com",
+    service: "tat",
+    version: "2020-10-28",
+};
+
+pub const TCS_SERVICE_MONITOR: TcsService = TcsService {
+    host: "monitor.tencentcloudapi.com",
+    service: "monitor",
+    version: "2018-07-24",
+};
+
+pub const TCS_SERVICE_BILLING: TcsService = TcsService {
+    host: "billing.tencentcloudapi.com",
+    service: "billing",
+    version: "2018-07-09",
+};
+
+pub const TCS_SERVICES: [TcsService; 7] = [TCS_SERVICE_CVM, TCS_SERVICE_VPC, TCS_SERVICE_CBS, TCS_SERVICE_CLB, TCS_SERVICE_TAT, TCS_SERVICE_MONITOR, TCS_SERVICE_BILLING];
+
+impl TcsService {
+    // 按服务名查找云产品接口, 例如 "cvm"
+    pub fn by_name(service: &str) -> Option<TcsService> {
+        TCS_SERVICES.into_iter().find(|tcs_service| tcs_service.service == service)
+    }
+}
+
+// 查询过滤条件
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsFilter {
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Values")]
+    pub values: Vec<String>,
+}
+
+impl TcsFilter {
+    pub fn new(name: &str, values: &[&str]) -> Self {
+        TcsFilter {
+            name: name.to_string(),
+            values: values.iter().map(|value| value.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TcsTag {
+    #[serde(rename = "Key")]
+    pub key: String,
+    #[serde(rename = "Value")]
+    pub value: String,
+}
+
+impl TcsTag {
+    pub fn new(key: &str, value: &str) -> Self {
+        TcsTag {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsVirtualPrivateCloud {
+    #[serde(rename = "VpcId")]
+    pub vpc_id: String,
+    #[serde(rename = "SubnetId")]
+    pub subnet_id: String,
+    #[serde(rename = "PrivateIpAddresses", skip_serializing_if = "Option::is_none")]
+    pub private_ip_addresses: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -196,6 +417,8 @@ pub struct DiskInfo {
     pub disk_id: Option<String>,
     #[serde(rename = "DiskSize")]
     pub disk_size: u16,
+    #[serde(rename = "DeleteWithInstance")]
+    pub delete_with_instance: Option<bool>,
 }
 
 #[allow(dead_code)]
@@ -376,7 +599,8 @@ impl TencentCloudApi {
     }
 
     // 可用机型列表
-    pub fn tcs_describe_zone_instance_config_infos(&mut self, tcs_data: &TcsData) -> Result<String, Box<dyn Error>> {
+    #[allow(clippy::needless_return)]
+    pub fn tcs_describe_zone_instance_config_infos(&mut self, tcs_data: &TcsData) -> Result<Vec<TcsInstanceTypeQuota>, Box<dyn Error>> {
         info!("[######][可用机型列表][@][tcs_describe_zone_instance_config_infos()][tcs_data: {:?}]", tcs_data);
 
         // 获取 TCS 配置数据...
@@ -426,9 +650,9 @@ impl TencentCloudApi {
                 debug!("[tcs_response_data: {}]", tcs_response_data);
 
                 // 解析为机型对象...
-                //let tcs_response_data: TcsResponseZoneInstanceConfig = serde_json::from_str(&tcs_response_data)?; //info!("[tcs_response_data: {:?}]", tcs_response_data);
+                let tcs_response_data: TcsResponseZoneInstanceConfig = serde_json::from_str(&tcs_response_data)?;
 
-                Ok("请求成功!".to_string())
+                Ok(tcs_response_data.instance_type_quota_set)
             }
             Result::Err(err) => {
                 error!("[tcs_request_err: {:?}]", err);
@@ -521,25 +745,25 @@ impl TencentCloudApi {
         // 获取 TCS 配置数据...
         // let tcs_title = tcs_data.tcs_title.as_str();
         let tcs_region = tcs_data.tcs_region.as_str();
-        let tcs_zone = tcs_data.tcs_zone.as_str();
-        let tcs_image_id = tcs_data.tcs_image_id.as_str();
+        // let tcs_zone = tcs_data.tcs_zone.as_str();
+        // let tcs_image_id = tcs_data.tcs_image_id.as_str();
         // let host_name = tcs_data.host_name.as_str();
-        let instance_name = tcs_data.instance_name.as_str();
+        // let instance_name = tcs_data.instance_name.as_str();
         // let instance_id = tcs_data.instance_id.as_str();
-        let password = tcs_data.password.as_str();
+        // let password = tcs_data.password.as_str();
         // let key_ids = tcs_data.key_ids.clone();
-        let tcs_info = tcs_data.tcs_info.clone();
-        let tcs_instance_charge_type = tcs_info.instance_charge_type;
+        // let tcs_info = tcs_data.tcs_info.clone();
+        // let tcs_instance_charge_type = tcs_info.instance_charge_type;
         // let tcs_instance_cpu = tcs_info.instance_cpu;
         // let tcs_instance_memory = tcs_info.instance_memory;
-        let tcs_max_unit_price = tcs_info.max_unit_price;
+        // let tcs_max_unit_price = tcs_info.max_unit_price;
 
         // 验证实例是否已创建...
         // 查询实例数据 - 可用实例列表...
-        match self.tcs_describe_instance_info(&tcs_data) {
+        match self.tcs_describe_instance_info(tcs_data) {
             Result::Ok(tcs_response_data) => {
                 // info!("[tcs_response_data: {:?}]", tcs_response_data);
-                if let Some(_) = tcs_response_data {
+                if tcs_response_data.is_some() {
                     return Result::Err("创建失败(实例已存在)!".into());
                 }
             }
@@ -550,7 +774,7 @@ impl TencentCloudApi {
         };
 
         // 挑选机型 - 可用机型列表...
-        let tcs_instance_info: TcsInstanceTypeQuota = match self.tcs_get_zone_instance_info(&tcs_data) {
+        let tcs_instance_info: TcsInstanceTypeQuota = match self.tcs_get_zone_instance_info(tcs_data) {
             Result::Ok(tcs_response_data) => {
                 info!("[tcs_response_data: {:?}]", tcs_response_data);
                 tcs_response_data
@@ -565,63 +789,13 @@ impl TencentCloudApi {
         let instance_type = tcs_instance_info.instance_type;
         // let instance_charge_type = tcs_instance_info.instance_charge_type;
 
-        // 配置请求参数...
-        let tcs_action = "RunInstances";
-
         // 请求参数
-        let payload = json!({
-            "Placement": {
-                "Zone": tcs_zone
-            },
-            "InstanceChargeType": tcs_instance_charge_type,
-            "InstanceType": instance_type,
-            "SystemDisk": {
-                "DiskType": "CLOUD_PREMIUM",
-                "DiskSize": 50
-            },
-            "DataDisks": [
-                {
-                    "DiskType": "CLOUD_PREMIUM",
-                    "DiskSize": 10,
-                }
-            ],
-            "InternetAccessible": {
-                "InternetChargeType": "TRAFFIC_POSTPAID_BY_HOUR",
-                "InternetMaxBandwidthOut": 10,
-                "PublicIpAssigned": true,
-            },
-            "InstanceName": instance_name,
-            "ImageId": tcs_image_id,
-            "InstanceMarketOptions": {
-                "SpotOptions": {
-                    "MaxPrice": format!{"{}", tcs_max_unit_price},
-                    "SpotInstanceType": "one-time"
-                },
-                "MarketType": "spot"
-            },
-            "LoginSettings": {
-                "Password": password,
-                //"KeyIds": key_ids
-            },
-            "EnhancedService": {
-                "SecurityService": {
-                    "Enabled": false
-                },
-                "MonitorService": {
-                    "Enabled": false
-                }
-            },
-            "InstanceCount": 1
-        });
-        info!("[payload: {}]", payload);
-
-        let api_payload = to_string(&payload).unwrap();
-        //debug!("[api_payload: {}]", api_payload);
+        let request = TcsRunInstancesRequest::new(tcs_data, instance_type.as_str());
 
         // 发起请求...
-        return match self.tcs_request_api(tcs_action, tcs_region, api_payload.as_str()) {
-            Result::Ok(tcs_response_data) => {
-                debug!("[tcs_response_data: {}]", tcs_response_data);
+        return match self.tcs_run_instances_request(tcs_region, &request) {
+            Result::Ok(instance_id_set) => {
+                info!("[instance_id_set: {:?}]", instance_id_set);
                 Ok("请求成功!".to_string())
             }
             Result::Err(err) => {
@@ -632,6 +806,7 @@ impl TencentCloudApi {
     }
 
     // 退还实例
+    #[allow(clippy::needless_return)]
     pub fn tcs_terminate_instances(&mut self, tcs_data: &TcsData) -> Result<String, Box<dyn Error>> {
         info!("[######][退还实例][@][tcs_terminate_instances()][tcs_data: {:?}]", tcs_data);
 
@@ -647,7 +822,7 @@ impl TencentCloudApi {
         // let key_ids = tcs_data.key_ids.clone();
 
         // 查询实例数据 - 可用实例列表...
-        let tcs_instance_info: TcsInstanceInfo = match self.tcs_describe_instance_info(&tcs_data) {
+        let tcs_instance_info: TcsInstanceInfo = match self.tcs_describe_instance_info(tcs_data) {
             Result::Ok(tcs_response_data) => {
                 // info!("[tcs_response_data: {:?}]", tcs_response_data);
                 if let Some(tcs_instance_info) = tcs_response_data {
@@ -662,28 +837,17 @@ impl TencentCloudApi {
             }
         };
 
+        // 禁止退还包年包月实例
+        tcs_termination_guard(&tcs_instance_info)?;
+
         // 实例信息
         let instance_id = tcs_instance_info.instance_id;
         // let instance_name = tcs_instance_info.instance_name;
-        let instance_charge_type = tcs_instance_info.instance_charge_type;
+        // let instance_charge_type = tcs_instance_info.instance_charge_type;
 
         // 配置请求参数...
         let tcs_action = "TerminateInstances";
 
-        // 禁止退还包年包月示例
-        let ins_list = vec!["ins-288qdetp", "ins-94c9ohbj", "ins-33r57jmx"];
-        if ins_list.contains(&instance_id.as_str()) {
-            error!("{}", "禁止退还包年包月实例!");
-            return Result::Err("请求不合法(..)!".into());
-        }
-
-        // 严禁删除包年包月实例
-        let ins_list = vec!["PREPAID"];
-        if ins_list.contains(&instance_charge_type.as_str()) {
-            error!("{}", "禁止销毁包年包月实例!");
-            return Result::Err("请求不合法(..)!".into());
-        }
-
         // 请求参数
         let payload = json!({
             "InstanceIds": [instance_id]
@@ -853,11 +1017,12 @@ impl TencentCloudApi {
         let host_name = tcs_data.host_name.as_str();
         // let instance_name = tcs_data.instance_name.as_str();
         // let instance_id = tcs_data.instance_id.as_str();
-        let password = tcs_data.password.as_str();
+        // let password = tcs_data.password.as_str();
         // let key_ids = tcs_data.key_ids.clone();
+        let login_settings = TcsLoginSettings::new(tcs_data);
 
         // 查询实例数据 - 可用实例列表...
-        let tcs_instance_info: TcsInstanceInfo = match self.tcs_describe_instance_info(&tcs_data) {
+        let tcs_instance_info: TcsInstanceInfo = match self.tcs_describe_instance_info(tcs_data) {
             Result::Ok(tcs_response_data) => {
                 // info!("[tcs_response_data: {:?}]", tcs_response_data);
                 if let Some(tcs_instance_info) = tcs_response_data {
@@ -877,38 +1042,22 @@ impl TencentCloudApi {
         // let instance_name = tcs_instance_info.instance_name;
         // let instance_charge_type = tcs_instance_info.instance_charge_type;
 
-        // 配置请求参数...
-        let tcs_action = "ResetInstance";
-
         // 请求参数
-        let payload = json!({
-            "HostName": host_name,
-            "InstanceId": instance_id,
-            "ImageId": tcs_image_id,
-            "LoginSettings": {
-                "Password": password,
-                //"KeyIds": key_ids
-            },
-            "EnhancedService": {
-                "SecurityService":{
-                    "Enabled":false
-                },
-                "MonitorService":{
-                    "Enabled":false
-                }
-            },
-        });
-        info!("[payload: {}]", payload);
-
-        let api_payload = to_string(&payload).unwrap();
-        //debug!("[api_payload: {}]", api_payload);
+        let request = TcsResetInstanceRequest {
+            instance_id,
+            image_id: Some(tcs_image_id.to_string()),
+            login_settings: Some(login_settings),
+            enhanced_service: Some(TcsEnhancedService {
+                security_service: TcsRunService { enabled: false },
+                monitor_service: TcsRunService { enabled: false },
+            }),
+            host_name: Some(host_name.to_string()),
+            ..Default::default()
+        };
 
         // 发起请求...
-        return match self.tcs_request_api(tcs_action, tcs_region, api_payload.as_str()) {
-            Result::Ok(tcs_response_data) => {
-                debug!("[tcs_response_data: {}]", tcs_response_data);
-                Ok("请求成功!".to_string())
-            }
+        return match self.tcs_reset_instance_request(tcs_region, &request, None) {
+            Result::Ok(_) => Ok("请求成功!".to_string()),
             Result::Err(err) => {
                 error!("[tcs_request_err: {:?}]", err);
                 Result::Err("请求失败(..)!".into())
@@ -917,7 +1066,7 @@ impl TencentCloudApi {
     }
 
     // 查看镜像列表
-    pub fn tcs_describe_images(&mut self, tcs_data: &TcsData) -> Result<String, Box<dyn Error>> {
+    pub fn tcs_describe_images(&mut self, tcs_data: &TcsData) -> Result<Vec<TcsImage>, Box<dyn Error>> {
         info!("[######][查看镜像列表][@][tcs_describe_images()][tcs_data: {:?}]", tcs_data);
 
         // 获取 TCS 配置数据...
@@ -931,39 +1080,52 @@ impl TencentCloudApi {
         // let password = tcs_data.password.as_str();
         // let key_ids = tcs_data.key_ids.clone();
 
-        // 配置请求参数...
-        let tcs_action = "DescribeImages";
+        // 查看全部镜像, 按类型等条件筛选请使用 tcs_describe_images_filter()...
+        self.tcs_describe_images_filter(tcs_region, &TcsImageFilter::default())
+    }
 
-        // 请求参数
-        let payload = json!({
-            "Limit": 100,
-        });
-        info!("[payload: {}]", payload);
+    // 发起请求并解析响应数据
+    pub(crate) fn tcs_request_action<T: DeserializeOwned>(&mut self, tcs_action: &str, tcs_region: &str, payload: &Value) -> Result<T, Box<dyn Error>> {
+        self.tcs_request_service_action(&TCS_SERVICE_CVM, tcs_action, tcs_region, payload)
+    }
 
-        let api_payload = to_string(&payload).unwrap();
-        //debug!("[api_payload: {}]", api_payload);
+    // 发起请求并解析响应数据(指定云产品)
+    pub(crate) fn tcs_request_service_action<T: DeserializeOwned>(&mut self, tcs_service: &TcsService, tcs_action: &str, tcs_region: &str, payload: &Value) -> Result<T, Box<dyn Error>> {
+        let api_payload = to_string(payload)?;
+        info!("[payload: {}]", mask_payload(&api_payload));
 
         // 发起请求...
-        return match self.tcs_request_api(tcs_action, tcs_region, api_payload.as_str()) {
+        match self.tcs_request_service_api(tcs_service, tcs_action, tcs_region, api_payload.as_str()) {
             Result::Ok(tcs_response_data) => {
-                debug!("[tcs_response_data: {}]", tcs_response_data);
-                Ok("请求成功!".to_string())
+                debug!("[tcs_response_data: {}]", mask_payload(&tcs_response_data));
+                Ok(serde_json::from_str(&tcs_response_data)?)
             }
             Result::Err(err) => {
                 error!("[tcs_request_err: {:?}]", err);
-                Result::Err("请求失败(..)!".into())
+                Result::Err(format!("请求失败({})!", err.code).into())
             }
-        };
+        }
     }
 
     // 发起请求
     pub fn tcs_request_api(&mut self, tcs_action: &str, tcs_region: &str, api_payload: &str) -> Result<String, TcsApiError> {
-        info!("[@@@@@@][发起请求][tcs_request_api()][tcs_action: {}][tcs_region: {}][api_payload: {}]", tcs_action, tcs_region, api_payload);
+        self.tcs_request_service_api(&TCS_SERVICE_CVM, tcs_action, tcs_region, api_payload)
+    }
+
+    // 发起请求(指定云产品)
+    pub fn tcs_request_service_api(&mut self, tcs_service: &TcsService, tcs_action: &str, tcs_region: &str, api_payload: &str) -> Result<String, TcsApiError> {
+        info!(
+            "[@@@@@@][发起请求][tcs_request_service_api()][tcs_service: {}][tcs_action: {}][tcs_region: {}][api_payload: {}]",
+            tcs_service.service,
+            tcs_action,
+            tcs_region,
+            mask_payload(api_payload)
+        );
 
         // 初始化请求参数
-        let tcs_host = "cvm.tencentcloudapi.com";
-        let tcs_service = "cvm";
-        let tcs_version = "2017-03-12";
+        let tcs_host = tcs_service.host;
+        let tcs_version = tcs_service.version;
+        let tcs_service = tcs_service.service;
 
         // 获取记录调用时间...
         let request_time = chrono::Local::now();
@@ -1007,7 +1169,7 @@ impl TencentCloudApi {
             tcs_region,
             "\"",
             " -d '",
-            api_payload,
+            mask_payload(api_payload),
             "'"
         );
         info!("[curl: ]\n{}", curl);
@@ -1022,7 +1184,10 @@ impl TencentCloudApi {
         headers.insert("X-TC-Action", tcs_action.parse().unwrap());
         headers.insert("X-TC-Timestamp", format!("{}", request_ts).parse().unwrap());
         headers.insert("X-TC-Version", tcs_version.parse().unwrap());
-        headers.insert("X-TC-Region", tcs_region.parse().unwrap());
+        // 部分接口(如查询地域列表)无需指定地域
+        if !tcs_region.is_empty() {
+            headers.insert("X-TC-Region", tcs_region.parse().unwrap());
+        }
 
         // Parse the string of data into serde_json::Value.
         // let api_payload_value: Value = serde_json::from_str(api_payload).unwrap();
@@ -1082,14 +1247,13 @@ impl TencentCloudApi {
             Result::Err(tcs_response_data) => {
                 debug!("tcs response parsing error unsuccessful![tcs_response_data: {:?}]", tcs_response_data);
                 // 自定义错误
-                let tcs_response_error = TcsResponseError {
+                TcsResponseError {
                     request_id: "REQUEST_ID_NONE".to_string(),
                     error: TcsApiError {
                         message: "tcs response parsing unsuccessful!".to_string(),
                         code: "REQUEST_ID_NONE".to_string(),
                     },
-                };
-                tcs_response_error
+                }
             }
         };
         // trace!("[@][tcs_response_error: {:?}]", tcs_response_error);
@@ -1099,10 +1263,18 @@ impl TencentCloudApi {
 
     // 设置签名
     // [接口鉴权 v3](https://cloud.tencent.com/document/api/213/30654)
+    #[allow(clippy::too_many_arguments)]
     pub fn request_tcs_signer(&mut self, tcs_host: &str, tcs_region: &str, tcs_action: &str, tcs_service: &str, api_payload: &str, request_ct: &str, request_ts: i64, request_date: &str) -> String {
         debug!(
             "[接口签名][request_tcs_signer()][tcs_host: {}][tcs_region: {}][tcs_action: {}][tcs_service: {}][api_payload: {}][request_ct: {}][request_ts: {}][request_date: {}]",
-            tcs_host, tcs_region, tcs_action, tcs_service, api_payload, request_ct, request_ts, request_date
+            tcs_host,
+            tcs_region,
+            tcs_action,
+            tcs_service,
+            mask_payload(api_payload),
+            request_ct,
+            request_ts,
+            request_date
         );
 
         // 签名算法...
@@ -1161,6 +1333,25 @@ impl TencentCloudApi {
     }
 }
 
+// 退还实例前检查: 禁止退还包年包月实例
+pub fn tcs_termination_guard(tcs_instance_info: &TcsInstanceInfo) -> Result<(), Box<dyn Error>> {
+    // 禁止退还包年包月示例
+    let ins_list = ["ins-288qdetp", "ins-94c9ohbj", "ins-33r57jmx"];
+    if ins_list.contains(&tcs_instance_info.instance_id.as_str()) {
+        error!("{}", "禁止退还包年包月实例!");
+        return Result::Err("请求不合法(..)!".into());
+    }
+
+    // 严禁删除包年包月实例
+    let ins_list = ["PREPAID"];
+    if ins_list.contains(&tcs_instance_info.instance_charge_type.as_str()) {
+        error!("{}", "禁止销毁包年包月实例!");
+        return Result::Err("请求不合法(..)!".into());
+    }
+
+    Ok(())
+}
+
 /// In case that the secret_key is leaked, we want to update the key at runtime.
 pub fn sha256_hex(payload: &str) -> String {
     let payload_digest = ring::digest::digest(&ring::digest::SHA256, payload.as_bytes());
@@ -1184,6 +1375,47 @@ pub fn hmac_256(secret_key: Vec<u8>, payload: &str) -> Vec<u8> {
     payload_digest.as_ref().to_vec()
 }
 
+// 隐藏请求/响应数据中的密码与私钥...
+pub fn mask_payload(api_payload: &str) -> String {
+    match serde_json::from_str::<Value>(api_payload) {
+        Result::Ok(mut value) => {
+            mask_value(&mut value);
+            value.to_string()
+        }
+        Result::Err(_) => api_payload.to_string(),
+    }
+}
+
+// 将密码与私钥替换为 TCS_MASKED_VALUE
+pub fn mask_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if SENSITIVE_KEYS.contains(&key.as_str()) {
+                    *value = Value::String(TCS_MASKED_VALUE.to_string());
+                } else {
+                    mask_value(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(mask_value),
+        _ => {}
+    }
+}
+
+// 是否包含已隐藏的密码或私钥
+pub fn contains_masked_value(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.iter().any(|(key, value)| (SENSITIVE_KEYS.contains(&key.as_str()) && value == TCS_MASKED_VALUE) || contains_masked_value(value)),
+        Value::Array(values) => values.iter().any(contains_masked_value),
+        _ => false,
+    }
+}
+
+pub const TCS_MASKED_VALUE: &str = "******";
+
+const SENSITIVE_KEYS: [&str; 2] = ["Password", "PrivateKey"];
+
 // 字节转十六进制表示...
 pub fn bytes_to_string(data_bytes: &[u8]) -> String {
     let data_vec: Vec<String> = data_bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
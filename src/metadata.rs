@@ -0,0 +1,198 @@
+use log::*;
+use reqwest::StatusCode;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+
+use std::error::Error;
+use std::time::Duration;
+
+use crate::TcsData;
+
+// 实例元数据服务地址(仅实例内部可访问)
+pub const TCS_METADATA_URL: &str = "http://metadata.tencentyun.com/latest/meta-data/";
+
+// 实例角色临时凭证
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TcsRoleCredentials {
+    #[serde(rename = "TmpSecretId")]
+    pub tmp_secret_id: String,
+    #[serde(rename = "TmpSecretKey")]
+    pub tmp_secret_key: String,
+    #[serde(rename = "Token")]
+    pub token: String,
+    #[serde(rename = "ExpiredTime")]
+    pub expired_time: i64,
+    #[serde(rename = "Expiration")]
+    pub expiration: Option<String>,
+    #[serde(rename = "Code")]
+    pub code: Option<String>,
+}
+
+// 日志中隐藏临时密钥
+impl std::fmt::Debug for TcsRoleCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcsRoleCredentials")
+            .field("tmp_secret_id", &self.tmp_secret_id)
+            .field("tmp_secret_key", &"******")
+            .field("token", &"******")
+            .field("expired_time", &self.expired_time)
+            .field("expiration", &self.expiration)
+            .finish()
+    }
+}
+
+// 实例元数据客户端
+pub struct TcsMetadataClient {
+    base_url: String,
+    client: Client,
+}
+
+impl TcsMetadataClient {
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        TcsMetadataClient::with_base_url(TCS_METADATA_URL, Duration::from_secs(2))
+    }
+
+    // 指定服务地址(本地测试)与超时时间
+    pub fn with_base_url(base_url: &str, timeout: Duration) -> Result<Self, Box<dyn Error>> {
+        let base_url = if base_url.ends_with('/') { base_url.to_string() } else { format!("{}/", base_url) };
+        let client = Client::builder().timeout(timeout).build()?;
+
+        Ok(TcsMetadataClient { base_url, client })
+    }
+
+    // 读取元数据, 不存在时返回 None
+    pub fn get(&self, path: &str) -> Result<Option<String>, Box<dyn Error>> {
+        let url = format!("{}{}", self.base_url, path.trim_start_matches('/'));
+        debug!("[TCS-METADATA][url: {}]", url);
+
+        let response = self.client.get(url.as_str()).send()?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Result::Err(format!("元数据请求失败({})!", response.status()).into());
+        }
+
+        Ok(Some(response.text()?.trim().to_string()))
+    }
+
+    // 读取必须存在的元数据
+    fn get_required(&self, path: &str) -> Result<String, Box<dyn Error>> {
+        self.get(path)?.ok_or_else(|| format!("元数据不存在({})!", path).into())
+    }
+
+    // 竞价实例回收时间, 未收到回收通知时返回 None
+    pub fn spot_termination_time(&self) -> Result<Option<String>, Box<dyn Error>> {
+        self.get("spot/termination-time")
+    }
+
+    pub fn instance_id(&self) -> Result<String, Box<dyn Error>> {
+        self.get_required("instance-id")
+    }
+
+    pub fn instance_name(&self) -> Result<String, Box<dyn Error>> {
+        self.get_required("instance-name")
+    }
+
+    pub fn instance_type(&self) -> Result<String, Box<dyn Error>> {
+        self.get_required("instance/instance-type")
+    }
+
+    pub fn image_id(&self) -> Result<String, Box<dyn Error>> {
+        self.get_required("instance/image-id")
+    }
+
+    pub fn region(&self) -> Result<String, Box<dyn Error>> {
+        self.get_required("placement/region")
+    }
+
+    pub fn zone(&self) -> Result<String, Box<dyn Error>> {
+        self.get_required("placement/zone")
+    }
+
+    pub fn private_ip(&self) -> Result<String, Box<dyn Error>> {
+        self.get_required("local-ipv4")
+    }
+
+    // 未绑定公网 IP 时返回 None
+    pub fn public_ip(&self) -> Result<Option<String>, Box<dyn Error>> {
+        self.get("public-ipv4")
+    }
+
+    // 实例绑定的角色名称, 未绑定角色时返回 None
+    pub fn role_name(&self) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(self.get("cam/security-credentials/")?.and_then(|roles| roles.lines().next().map(|role| role.trim().to_string())).filter(|role| !role.is_empty()))
+    }
+
+    // 实例角色临时凭证
+    pub fn role_credentials(&self, role_name: &str) -> Result<TcsRoleCredentials, Box<dyn Error>> {
+        let content = self.get_required(format!("cam/security-credentials/{}", role_name).as_str())?;
+        let credentials: TcsRoleCredentials = serde_json::from_str(&content)?;
+        if let Some(code) = &credentials.code
+            && code != "Success"
+        {
+            return Result::Err(format!("获取角色凭证失败({})!", code).into());
+        }
+
+        Ok(credentials)
+    }
+
+    // 使用实例所在的地域与可用区填充配置
+    pub fn fill_tcs_data(&self, tcs_data: &mut TcsData) -> Result<(), Box<dyn Error>> {
+        tcs_data.tcs_region = self.region()?;
+        tcs_data.tcs_zone = self.zone()?;
+
+        info!("[######][填充实例位置][@][fill_tcs_data()][tcs_region: {}][tcs_zone: {}]", tcs_data.tcs_region, tcs_data.tcs_zone);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // 本地模拟元数据服务, 每个连接处理一个请求
+    fn serve(requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/latest/meta-data/", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut request_line = String::new();
+                BufReader::new(&stream).read_line(&mut request_line).unwrap();
+                let path = request_line.split_whitespace().nth(1).unwrap_or_default().trim_start_matches("/latest/meta-data/");
+
+                let (status, body) = match path {
+                    "placement/region" => ("200 OK", "ap-guangzhou"),
+                    "placement/zone" => ("200 OK", "ap-guangzhou-3"),
+                    "cam/security-credentials/" => ("200 OK", "agent-role"),
+                    "cam/security-credentials/agent-role" => ("200 OK", r#"{"TmpSecretId":"AKID","TmpSecretKey":"hidden-key","Token":"token","ExpiredTime":1700000000,"Expiration":"2023-11-14T22:13:20Z","Code":"Success"}"#),
+                    _ => ("404 Not Found", ""),
+                };
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        base_url
+    }
+
+    #[test]
+    fn metadata_base_url_override() {
+        let metadata = TcsMetadataClient::with_base_url(serve(5).as_str(), Duration::from_secs(2)).unwrap();
+
+        assert_eq!(metadata.region().unwrap(), "ap-guangzhou");
+        assert_eq!(metadata.zone().unwrap(), "ap-guangzhou-3");
+        assert_eq!(metadata.spot_termination_time().unwrap(), None);
+
+        let role_name = metadata.role_name().unwrap().unwrap();
+        let credentials = metadata.role_credentials(role_name.as_str()).unwrap();
+        assert_eq!(credentials.tmp_secret_id, "AKID");
+        assert!(!format!("{:?}", credentials).contains("hidden-key"));
+    }
+}
@@ -0,0 +1,198 @@
+use chrono::{DateTime, Local};
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::error::Error;
+
+use crate::{TCS_SERVICE_MONITOR, TcsInstanceInfo, TencentCloudApi};
+
+// 云服务器监控指标(命名空间 QCE/CVM)
+// [云服务器监控指标](https://cloud.tencent.com/document/product/248/6843)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TcsCvmMetric {
+    // CPU 利用率(%)
+    CpuUsage,
+    // 内存利用率(%), 需安装监控组件
+    MemUsage,
+    // 内网入/出带宽(Mbps)
+    LanIntraffic,
+    LanOuttraffic,
+    // 外网入/出带宽(Mbps)
+    WanIntraffic,
+    WanOuttraffic,
+    // 磁盘利用率(%)
+    CvmDiskUsage,
+}
+
+impl TcsCvmMetric {
+    pub fn metric_name(&self) -> &'static str {
+        match self {
+            TcsCvmMetric::CpuUsage => "CpuUsage",
+            TcsCvmMetric::MemUsage => "MemUsage",
+            TcsCvmMetric::LanIntraffic => "LanIntraffic",
+            TcsCvmMetric::LanOuttraffic => "LanOuttraffic",
+            TcsCvmMetric::WanIntraffic => "WanIntraffic",
+            TcsCvmMetric::WanOuttraffic => "WanOuttraffic",
+            TcsCvmMetric::CvmDiskUsage => "CvmDiskUsage",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsDimension {
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Value")]
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsDataPoint {
+    #[serde(rename = "Dimensions")]
+    pub dimensions: Vec<TcsDimension>,
+    #[serde(rename = "Timestamps", default, deserialize_with = "crate::null_default")]
+    pub timestamps: Vec<f64>,
+    #[serde(rename = "Values", default, deserialize_with = "crate::null_default")]
+    pub values: Vec<f64>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseGetMonitorData {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "Period")]
+    pub period: u32,
+    #[serde(rename = "MetricName")]
+    pub metric_name: String,
+    #[serde(rename = "DataPoints")]
+    pub data_points: Vec<TcsDataPoint>,
+}
+
+// 单个实例的指标时间序列
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsMetricSeries {
+    pub instance_id: String,
+    pub metric_name: String,
+    // 统计周期(秒)
+    pub period: u32,
+    // (时间戳, 数值)
+    pub points: Vec<(i64, f64)>,
+}
+
+impl TcsMetricSeries {
+    pub fn average(&self) -> Option<f64> {
+        if self.points.is_empty() {
+            return None;
+        }
+        Some(self.points.iter().map(|(_, value)| value).sum::<f64>() / self.points.len() as f64)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.points.iter().map(|(_, value)| *value).reduce(f64::max)
+    }
+}
+
+// 单次请求最多查询 10 个实例
+pub const TCS_MONITOR_BATCH: usize = 10;
+
+// 平均 CPU 利用率低于阈值的实例, 无监控数据的实例不计入
+pub fn select_idle_instances(series_set: &[TcsMetricSeries], cpu_threshold: f64) -> Vec<(String, f64)> {
+    let mut idle: Vec<(String, f64)> = series_set
+        .iter()
+        .filter_map(|series| series.average().map(|average| (series.instance_id.clone(), average)))
+        .filter(|(_, average)| *average < cpu_threshold)
+        .collect();
+    idle.sort_by(|a, b| a.1.total_cmp(&b.1));
+    idle
+}
+
+impl TencentCloudApi {
+    // 拉取实例监控数据, period 为统计周期(60 / 300 / 3600 / 86400 秒)
+    pub fn tcs_get_monitor_data(
+        &mut self,
+        tcs_region: &str,
+        metric: TcsCvmMetric,
+        instance_ids: &[String],
+        period: u32,
+        start_time: DateTime<Local>,
+        end_time: DateTime<Local>,
+    ) -> Result<Vec<TcsMetricSeries>, Box<dyn Error>> {
+        info!(
+            "[######][拉取监控数据][@][tcs_get_monitor_data()][tcs_region: {}][metric: {}][instance_ids: {:?}][period: {}][start_time: {}][end_time: {}]",
+            tcs_region,
+            metric.metric_name(),
+            instance_ids,
+            period,
+            start_time,
+            end_time
+        );
+
+        let mut series_set = vec![];
+        for chunk in instance_ids.chunks(TCS_MONITOR_BATCH) {
+            let instances: Vec<serde_json::Value> = chunk.iter().map(|instance_id| json!({ "Dimensions": [{ "Name": "InstanceId", "Value": instance_id }] })).collect();
+            let payload = json!({
+                "Namespace": "QCE/CVM",
+                "MetricName": metric.metric_name(),
+                "Instances": instances,
+                "Period": period,
+                "StartTime": start_time.to_rfc3339(),
+                "EndTime": end_time.to_rfc3339()
+            });
+
+            let tcs_response_data: TcsResponseGetMonitorData = self.tcs_request_service_action(&TCS_SERVICE_MONITOR, "GetMonitorData", tcs_region, &payload)?;
+            for data_point in tcs_response_data.data_points {
+                let Some(instance_id) = data_point.dimensions.iter().find(|dimension| dimension.name == "InstanceId").map(|dimension| dimension.value.clone()) else { continue };
+                series_set.push(TcsMetricSeries {
+                    instance_id,
+                    metric_name: tcs_response_data.metric_name.clone(),
+                    period: tcs_response_data.period,
+                    points: data_point.timestamps.iter().zip(data_point.values.iter()).map(|(timestamp, value)| (*timestamp as i64, *value)).collect(),
+                });
+            }
+        }
+
+        Ok(series_set)
+    }
+
+    // 查找最近 hours 小时平均 CPU 利用率低于阈值的实例(instance_set 可取自 tcs_describe_instance_list), 按平均值升序返回
+    pub fn tcs_find_idle_instances(&mut self, tcs_region: &str, instance_set: &[TcsInstanceInfo], cpu_threshold: f64, hours: i64) -> Result<Vec<(TcsInstanceInfo, f64)>, Box<dyn Error>> {
+        info!("[######][查找空闲实例][@][tcs_find_idle_instances()][tcs_region: {}][cpu_threshold: {}][hours: {}]", tcs_region, cpu_threshold, hours);
+
+        let instance_ids: Vec<String> = instance_set.iter().filter(|instance| instance.instance_state == "RUNNING").map(|instance| instance.instance_id.clone()).collect();
+        if instance_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // 时间跨度较长时使用小时粒度, 减少数据点
+        let period = if hours > 24 { 3600 } else { 300 };
+        let end_time = Local::now();
+        let start_time = end_time - chrono::Duration::hours(hours);
+        let series_set = self.tcs_get_monitor_data(tcs_region, TcsCvmMetric::CpuUsage, &instance_ids, period, start_time, end_time)?;
+
+        let idle = select_idle_instances(&series_set, cpu_threshold);
+        Ok(idle
+            .into_iter()
+            .filter_map(|(instance_id, average)| instance_set.iter().find(|instance| instance.instance_id == instance_id).map(|instance| (instance.clone(), average)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn idle_instances() {
+        let series = |instance_id: &str, values: &[f64]| TcsMetricSeries {
+            instance_id: instance_id.to_string(),
+            metric_name: "CpuUsage".to_string(),
+            period: 300,
+            points: values.iter().enumerate().map(|(index, value)| (index as i64 * 300, *value)).collect(),
+        };
+
+        let series_set = vec![series("ins-1", &[1.0, 3.0]), series("ins-2", &[50.0, 70.0]), series("ins-3", &[]), series("ins-4", &[0.5])];
+        assert_eq!(series_set[1].max(), Some(70.0));
+        assert_eq!(select_idle_instances(&series_set, 5.0), vec![("ins-4".to_string(), 0.5), ("ins-1".to_string(), 2.0)]);
+    }
+}
@@ -0,0 +1,300 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, to_value};
+
+use std::error::Error;
+
+use crate::{FleetAction, FleetSpec, TCS_SERVICE_CVM, TcsLoginSettings, TcsPrice, TcsRunInstancesRequest, TcsService, TcsWaiter, TencentCloudApi, contains_masked_value, mask_value, tcs_termination_guard};
+
+// 计划中的单次接口调用
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsPlannedCall {
+    // 云产品服务名, 例如 "cvm"
+    #[serde(default = "default_tcs_service")]
+    pub tcs_service: String,
+    pub tcs_action: String,
+    pub tcs_region: String,
+    pub description: String,
+    pub payload: Value,
+    pub estimated_price: Option<TcsPrice>,
+    // 创建实例后等待新实例达到该状态, 再执行后续调用
+    #[serde(default)]
+    pub wait_instance_state: Option<String>,
+}
+
+// 变更计划: 先生成并评审, 再原样执行
+// 计划中的密码与私钥均已隐藏, 执行时再注入登录设置
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsPlan {
+    pub title: String,
+    pub created_time: String,
+    pub calls: Vec<TcsPlannedCall>,
+}
+
+impl TcsPlan {
+    pub fn new(title: &str) -> Self {
+        TcsPlan {
+            title: title.to_string(),
+            created_time: chrono::Local::now().to_rfc3339(),
+            calls: vec![],
+        }
+    }
+
+    pub fn push(&mut self, tcs_service: &TcsService, tcs_action: &str, tcs_region: &str, description: &str, mut payload: Value, estimated_price: Option<TcsPrice>) {
+        mask_value(&mut payload);
+        self.calls.push(TcsPlannedCall {
+            tcs_service: tcs_service.service.to_string(),
+            tcs_action: tcs_action.to_string(),
+            tcs_region: tcs_region.to_string(),
+            description: description.to_string(),
+            payload,
+            estimated_price,
+            wait_instance_state: None,
+        });
+    }
+
+    // 上一次调用(创建实例)完成后等待新实例达到指定状态
+    pub fn wait_instance_state(&mut self, instance_state: &str) {
+        if let Some(call) = self.calls.last_mut() {
+            call.wait_instance_state = Some(instance_state.to_string());
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    // 按计费单位汇总的预估单价(折后), 例如 [("HOUR", 0.22)]
+    pub fn estimated_unit_prices(&self) -> Vec<(String, f32)> {
+        let mut unit_prices: Vec<(String, f32)> = vec![];
+        for estimated_price in self.calls.iter().filter_map(|call| call.estimated_price.as_ref()) {
+            for item_price in [&estimated_price.instance_price, &estimated_price.bandwidth_price].into_iter().flatten() {
+                if let Some((charge_unit, unit_price_discount)) = item_price.discounted_unit_price() {
+                    match unit_prices.iter_mut().find(|(unit, _)| unit == charge_unit) {
+                        Some((_, total)) => *total += unit_price_discount,
+                        None => unit_prices.push((charge_unit.to_string(), unit_price_discount)),
+                    }
+                }
+            }
+        }
+        unit_prices
+    }
+
+    // 生成可读的计划说明(隐藏密码等敏感数据)
+    pub fn render(&self) -> String {
+        let mut lines = vec![format!("# {}", self.title), format!("# created: {}", self.created_time), String::new()];
+
+        if self.calls.is_empty() {
+            lines.push("无变更".to_string());
+        }
+
+        for (index, call) in self.calls.iter().enumerate() {
+            lines.push(format!("{}. {}:{} [{}] {}", index + 1, call.tcs_service, call.tcs_action, call.tcs_region, call.description));
+
+            if let Some(estimated_price) = &call.estimated_price {
+                let items = [("实例", &estimated_price.instance_price), ("带宽", &estimated_price.bandwidth_price)];
+                for (name, item_price) in items {
+                    let Some(item_price) = item_price else { continue };
+                    match (item_price.discounted_unit_price(), item_price.discounted_total_price()) {
+                        (Some((charge_unit, unit_price_discount)), _) => lines.push(format!("   预估{}价格: {}/{}", name, unit_price_discount, charge_unit)),
+                        (None, Some(discount_price)) => lines.push(format!("   预估{}价格: {}", name, discount_price)),
+                        (None, None) => {}
+                    }
+                }
+            }
+
+            if let Some(instance_state) = &call.wait_instance_state {
+                lines.push(format!("   等待新实例状态: {}", instance_state));
+            }
+
+            // 手工编辑的计划文件可能包含明文密码...
+            let mut payload = call.payload.clone();
+            mask_value(&mut payload);
+            for line in serde_json::to_string_pretty(&payload).unwrap_or_default().lines() {
+                lines.push(format!("   {}", line));
+            }
+        }
+
+        let unit_prices = self.estimated_unit_prices();
+        if !unit_prices.is_empty() {
+            lines.push(String::new());
+            let totals: Vec<String> = unit_prices.iter().map(|(charge_unit, total)| format!("{}/{}", total, charge_unit)).collect();
+            lines.push(format!("预估新增费用: {}", totals.join(", ")));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(content)?)
+    }
+}
+
+impl TencentCloudApi {
+    // 计划创建实例(附带询价结果, 询价失败时不影响计划)
+    pub fn tcs_plan_run_instances(&mut self, plan: &mut TcsPlan, tcs_region: &str, request: &TcsRunInstancesRequest, description: &str) -> Result<(), Box<dyn Error>> {
+        let estimated_price = match self.tcs_inquiry_price_run_instances(tcs_region, request) {
+            Result::Ok(price) => Some(price),
+            Result::Err(err) => {
+                warn!("[询价失败][instance_name: {}][err: {}]", request.instance_name, err);
+                None
+            }
+        };
+
+        plan.push(&TCS_SERVICE_CVM, "RunInstances", tcs_region, description, to_value(request)?, estimated_price);
+        Ok(())
+    }
+
+    // 计划退还实例(生成计划时即检查退还保护)
+    pub fn tcs_plan_terminate_instances(&mut self, plan: &mut TcsPlan, tcs_region: &str, instance_ids: &[String], description: &str) -> Result<(), Box<dyn Error>> {
+        let instance_set = self.tcs_describe_instances_by_ids(tcs_region, instance_ids)?;
+        let missing_ids: Vec<&String> = instance_ids.iter().filter(|instance_id| !instance_set.iter().any(|instance| &instance.instance_id == *instance_id)).collect();
+        if !missing_ids.is_empty() {
+            return Result::Err(format!("实例不存在({:?})!", missing_ids).into());
+        }
+        for instance in &instance_set {
+            tcs_termination_guard(instance)?;
+        }
+
+        plan.push(&TCS_SERVICE_CVM, "TerminateInstances", tcs_region, description, serde_json::json!({ "InstanceIds": instance_ids }), None);
+        Ok(())
+    }
+
+    // 生成实例组变更的接口调用计划
+    pub fn tcs_plan_fleet_calls(&mut self, spec: &FleetSpec) -> Result<TcsPlan, Box<dyn Error>> {
+        let fleet_plan = self.tcs_plan_fleet(spec)?;
+
+        let tcs_region = spec.tcs_region.as_str();
+        let mut plan = TcsPlan::new(format!("实例组 {} ({})", spec.fleet_name, tcs_region).as_str());
+        for action in &fleet_plan.actions {
+            match action {
+                FleetAction::Create { instance_name } => {
+                    let description = format!("创建实例 {}", instance_name);
+                    self.tcs_plan_run_instances(&mut plan, tcs_region, &spec.launch_request(instance_name), description.as_str())?;
+                }
+                FleetAction::Replace { instance_id, instance_name, reason } => {
+                    let description = format!("重建实例 {} ({}): 创建", instance_name, reason);
+                    self.tcs_plan_run_instances(&mut plan, tcs_region, &spec.launch_request(instance_name), description.as_str())?;
+                    // 新实例运行后再退还旧实例
+                    plan.wait_instance_state("RUNNING");
+
+                    let description = format!("重建实例 {} ({}): 退还 {}", instance_name, reason, instance_id);
+                    self.tcs_plan_terminate_instances(&mut plan, tcs_region, std::slice::from_ref(instance_id), description.as_str())?;
+                }
+                FleetAction::Terminate { instance_id, instance_name, reason } => {
+                    let description = format!("退还实例 {} {} ({})", instance_name, instance_id, reason);
+                    self.tcs_plan_terminate_instances(&mut plan, tcs_region, std::slice::from_ref(instance_id), description.as_str())?;
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    // 按顺序执行计划中的接口调用, 出错时立即停止
+    // login_settings: 替换计划中已隐藏的登录设置
+    // waiter: 等待新实例状态时使用
+    pub fn tcs_apply_plan(&mut self, plan: &TcsPlan, login_settings: Option<&TcsLoginSettings>, waiter: &TcsWaiter) -> Result<Vec<Value>, Box<dyn Error>> {
+        info!("[######][执行变更计划][@][tcs_apply_plan()][title: {}][calls: {}]", plan.title, plan.calls.len());
+
+        // 执行前检查, 避免执行到一半才发现缺少登录设置...
+        let payloads = plan.calls.iter().map(|call| inject_login_settings(&call.payload, login_settings)).collect::<Result<Vec<Value>, Box<dyn Error>>>()?;
+
+        let tcs_services = plan
+            .calls
+            .iter()
+            .map(|call| TcsService::by_name(call.tcs_service.as_str()).ok_or_else(|| format!("未知的云产品服务({})!", call.tcs_service).into()))
+            .collect::<Result<Vec<TcsService>, Box<dyn Error>>>()?;
+
+        let mut responses = vec![];
+        for ((call, payload), tcs_service) in plan.calls.iter().zip(payloads).zip(tcs_services) {
+            info!("[call: {} {} {}]", call.tcs_action, call.tcs_region, call.description);
+
+            // 退还实例重新检查退还保护...
+            if call.tcs_service == TCS_SERVICE_CVM.service && call.tcs_action == "TerminateInstances" {
+                let instance_ids: Vec<String> = serde_json::from_value(call.payload["InstanceIds"].clone())?;
+                self.tcs_terminate_instances_by_ids(call.tcs_region.as_str(), &instance_ids)?;
+                responses.push(Value::Null);
+                continue;
+            }
+
+            let response: Value = self.tcs_request_service_action(&tcs_service, call.tcs_action.as_str(), call.tcs_region.as_str(), &payload)?;
+
+            if let Some(instance_state) = &call.wait_instance_state {
+                let instance_ids: Vec<String> = serde_json::from_value(response["InstanceIdSet"].clone())?;
+                for instance_id in &instance_ids {
+                    self.tcs_wait_instance_state(call.tcs_region.as_str(), instance_id.as_str(), instance_state.as_str(), waiter)?;
+                }
+            }
+            responses.push(response);
+        }
+
+        Ok(responses)
+    }
+}
+
+fn default_tcs_service() -> String {
+    TCS_SERVICE_CVM.service.to_string()
+}
+
+// 使用执行时提供的登录设置替换计划中已隐藏的登录设置
+fn inject_login_settings(payload: &Value, login_settings: Option<&TcsLoginSettings>) -> Result<Value, Box<dyn Error>> {
+    let mut payload = payload.clone();
+    if let Some(login_settings) = login_settings
+        && payload.get("LoginSettings").is_some_and(contains_masked_value)
+    {
+        payload["LoginSettings"] = to_value(login_settings)?;
+    }
+
+    if contains_masked_value(&payload) {
+        return Result::Err("计划中的密码或私钥已隐藏, 请在执行时提供登录设置!".into());
+    }
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn render_and_roundtrip_plan() {
+        let price: TcsPrice = serde_json::from_value(json!({
+            "InstancePrice": { "UnitPrice": 0.5, "UnitPriceDiscount": 0.1, "Discount": 20.0, "ChargeUnit": "HOUR" }
+        }))
+        .unwrap();
+
+        let mut plan = TcsPlan::new("实例组 web (ap-guangzhou)");
+        plan.push(&TCS_SERVICE_CVM, "RunInstances", "ap-guangzhou", "创建实例 web-0", json!({ "InstanceName": "web-0", "LoginSettings": { "Password": "secret" } }), Some(price.clone()));
+        plan.push(&TCS_SERVICE_CVM, "RunInstances", "ap-guangzhou", "创建实例 web-1", json!({ "InstanceName": "web-1" }), Some(price));
+        plan.wait_instance_state("RUNNING");
+        plan.push(&TCS_SERVICE_CVM, "TerminateInstances", "ap-guangzhou", "退还实例 web-5", json!({ "InstanceIds": ["ins-5"] }), None);
+
+        let rendered = plan.render();
+        assert!(rendered.contains("1. cvm:RunInstances [ap-guangzhou] 创建实例 web-0"));
+        assert!(rendered.contains("预估实例价格: 0.1/HOUR"));
+        assert!(rendered.contains("等待新实例状态: RUNNING"));
+        assert!(rendered.contains("预估新增费用: 0.2/HOUR"));
+        assert!(!rendered.contains("secret"));
+
+        // 计划文件不包含密码, 执行时注入
+        let plan_json = plan.to_json().unwrap();
+        assert!(!plan_json.contains("secret"));
+
+        let plan = TcsPlan::from_json(&plan_json).unwrap();
+        assert_eq!(plan.calls.len(), 3);
+        assert!(plan.calls[2].estimated_price.is_none());
+        assert_eq!(plan.calls[1].wait_instance_state.as_deref(), Some("RUNNING"));
+        assert!(plan.calls[0].wait_instance_state.is_none());
+        assert_eq!(plan.calls[2].tcs_service, "cvm");
+        assert!(inject_login_settings(&plan.calls[0].payload, None).is_err());
+
+        let payload = inject_login_settings(&plan.calls[0].payload, Some(&TcsLoginSettings::password("secret"))).unwrap();
+        assert_eq!(payload["LoginSettings"]["Password"], "secret");
+        assert_eq!(inject_login_settings(&plan.calls[1].payload, None).unwrap(), plan.calls[1].payload);
+    }
+}
@@ -0,0 +1,208 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{TcsData, TencentCloudApi};
+
+// 地域信息
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsRegionInfo {
+    #[serde(rename = "Region")]
+    pub region: String,
+    #[serde(rename = "RegionName")]
+    pub region_name: String,
+    #[serde(rename = "RegionState")]
+    pub region_state: String,
+}
+
+// 可用区信息
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsZoneInfo {
+    #[serde(rename = "Zone")]
+    pub zone: String,
+    #[serde(rename = "ZoneName")]
+    pub zone_name: String,
+    #[serde(rename = "ZoneId")]
+    pub zone_id: String,
+    #[serde(rename = "ZoneState")]
+    pub zone_state: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeRegions {
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "RegionSet")]
+    pub region_set: Vec<TcsRegionInfo>,
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeZones {
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "ZoneSet")]
+    pub zone_set: Vec<TcsZoneInfo>,
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+}
+
+// 地域标识, 例如 ap-guangzhou, na-siliconvalley, ap-shanghai-fsi
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct TcsRegion(String);
+
+impl TcsRegion {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    // 可用区是否属于该地域, 例如 ap-guangzhou-3
+    pub fn contains_zone(&self, zone: &str) -> bool {
+        match zone.strip_prefix(self.0.as_str()).and_then(|suffix| suffix.strip_prefix('-')) {
+            Some(index) => !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()),
+            None => false,
+        }
+    }
+}
+
+impl FromStr for TcsRegion {
+    type Err = String;
+
+    fn from_str(region: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = region.split('-').collect();
+        let valid = (2..=3).contains(&parts.len()) && parts[0].len() == 2 && parts.iter().all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_lowercase()));
+        if !valid {
+            return Result::Err(format!("地域格式错误({})!", region));
+        }
+
+        Ok(TcsRegion(region.to_string()))
+    }
+}
+
+impl TryFrom<String> for TcsRegion {
+    type Error = String;
+
+    fn try_from(region: String) -> Result<Self, Self::Error> {
+        region.parse()
+    }
+}
+
+impl From<TcsRegion> for String {
+    fn from(region: TcsRegion) -> Self {
+        region.0
+    }
+}
+
+impl fmt::Display for TcsRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+impl TencentCloudApi {
+    // 查询地域列表
+    pub fn tcs_describe_regions(&mut self) -> Result<Vec<TcsRegionInfo>, Box<dyn Error>> {
+        info!("[######][查询地域列表][@][tcs_describe_regions()]");
+
+        // 地域列表接口无需指定地域...
+        let tcs_response_data: TcsResponseDescribeRegions = self.tcs_request_action("DescribeRegions", "", &json!({}))?;
+        Ok(tcs_response_data.region_set)
+    }
+
+    // 查询地域下的可用区列表
+    pub fn tcs_describe_zones(&mut self, tcs_region: &TcsRegion) -> Result<Vec<TcsZoneInfo>, Box<dyn Error>> {
+        info!("[######][查询可用区列表][@][tcs_describe_zones()][tcs_region: {}]", tcs_region);
+
+        let tcs_response_data: TcsResponseDescribeZones = self.tcs_request_action("DescribeZones", tcs_region.as_str(), &json!({}))?;
+        Ok(tcs_response_data.zone_set)
+    }
+}
+
+// 地域与可用区目录(可用区按需查询并缓存)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsRegionCatalog {
+    pub regions: Vec<TcsRegionInfo>,
+    pub zones: HashMap<String, Vec<TcsZoneInfo>>,
+}
+
+impl TcsRegionCatalog {
+    pub fn load(api: &mut TencentCloudApi) -> Result<Self, Box<dyn Error>> {
+        Ok(TcsRegionCatalog {
+            regions: api.tcs_describe_regions()?,
+            zones: HashMap::new(),
+        })
+    }
+
+    // 可用地域
+    pub fn available_regions(&self) -> Vec<&TcsRegionInfo> {
+        self.regions.iter().filter(|region| region.region_state == "AVAILABLE").collect()
+    }
+
+    pub fn region(&self, region: &str) -> Option<&TcsRegionInfo> {
+        self.regions.iter().find(|info| info.region == region)
+    }
+
+    // 校验地域是否存在且可用
+    pub fn check_region(&self, region: &str) -> Result<TcsRegion, Box<dyn Error>> {
+        let tcs_region: TcsRegion = region.parse()?;
+        match self.region(region) {
+            Some(info) if info.region_state == "AVAILABLE" => Ok(tcs_region),
+            Some(info) => Result::Err(format!("地域不可用({}: {})!", region, info.region_state).into()),
+            None => Result::Err(format!("地域不存在({})!", region).into()),
+        }
+    }
+
+    // 地域下的可用区(首次查询后缓存)
+    pub fn zones(&mut self, api: &mut TencentCloudApi, region: &str) -> Result<&[TcsZoneInfo], Box<dyn Error>> {
+        let tcs_region = self.check_region(region)?;
+        if !self.zones.contains_key(region) {
+            let zone_set = api.tcs_describe_zones(&tcs_region)?;
+            self.zones.insert(region.to_string(), zone_set);
+        }
+
+        Ok(self.zones[region].as_slice())
+    }
+
+    // 校验可用区是否属于地域且可用
+    pub fn check_zone(&mut self, api: &mut TencentCloudApi, region: &str, zone: &str) -> Result<(), Box<dyn Error>> {
+        let zone_set = self.zones(api, region)?;
+        match zone_set.iter().find(|info| info.zone == zone) {
+            Some(info) if info.zone_state == "AVAILABLE" => Ok(()),
+            Some(info) => Result::Err(format!("可用区不可用({}: {})!", zone, info.zone_state).into()),
+            None => Result::Err(format!("可用区不存在({}/{})!", region, zone).into()),
+        }
+    }
+
+    // 校验配置中的地域与可用区
+    pub fn check_tcs_data(&mut self, api: &mut TencentCloudApi, tcs_data: &TcsData) -> Result<(), Box<dyn Error>> {
+        self.check_zone(api, tcs_data.tcs_region.as_str(), tcs_data.tcs_zone.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_region() {
+        let region: TcsRegion = "ap-guangzhou".parse().unwrap();
+        assert!(region.contains_zone("ap-guangzhou-3"));
+        assert!(!region.contains_zone("ap-guangzhou"));
+        assert!(!region.contains_zone("ap-guangzhou-fsi-1"));
+
+        assert!("ap-shanghai-fsi".parse::<TcsRegion>().is_ok());
+        assert!("na-siliconvalley".parse::<TcsRegion>().is_ok());
+        assert!("guangzhou".parse::<TcsRegion>().is_err());
+        assert!("ap-Guangzhou".parse::<TcsRegion>().is_err());
+        assert!("ap-guangzhou-3".parse::<TcsRegion>().is_err());
+
+        assert!(serde_json::from_str::<TcsRegion>("\"ap guangzhou\"").is_err());
+    }
+}
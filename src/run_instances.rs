@@ -0,0 +1,368 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::to_value;
+
+use std::error::Error;
+
+use crate::{TcsData, TcsInstanceTypeQuotaPrice, TcsTag, TcsVirtualPrivateCloud, TencentCloudApi, encode_user_data};
+
+// 创建实例请求参数
+// [创建实例](https://cloud.tencent.com/document/api/213/15730)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsRunInstancesRequest {
+    #[serde(rename = "Placement")]
+    pub placement: TcsPlacement,
+    #[serde(rename = "InstanceChargeType")]
+    pub instance_charge_type: String,
+    #[serde(rename = "InstanceType")]
+    pub instance_type: String,
+    #[serde(rename = "SystemDisk")]
+    pub system_disk: TcsDisk,
+    #[serde(rename = "DataDisks", default, skip_serializing_if = "Vec::is_empty")]
+    pub data_disks: Vec<TcsDisk>,
+    #[serde(rename = "InternetAccessible")]
+    pub internet_accessible: TcsInternetAccessible,
+    // 未指定时使用默认私有网络
+    #[serde(rename = "VirtualPrivateCloud", skip_serializing_if = "Option::is_none")]
+    pub virtual_private_cloud: Option<TcsVirtualPrivateCloud>,
+    #[serde(rename = "SecurityGroupIds", default, skip_serializing_if = "Vec::is_empty")]
+    pub security_group_ids: Vec<String>,
+    #[serde(rename = "InstanceName")]
+    pub instance_name: String,
+    #[serde(rename = "ImageId")]
+    pub image_id: String,
+    #[serde(rename = "InstanceMarketOptions", skip_serializing_if = "Option::is_none")]
+    pub instance_market_options: Option<TcsInstanceMarketOptions>,
+    #[serde(rename = "LoginSettings")]
+    pub login_settings: TcsLoginSettings,
+    #[serde(rename = "EnhancedService")]
+    pub enhanced_service: TcsEnhancedService,
+    #[serde(rename = "InstanceCount")]
+    pub instance_count: u32,
+    // Base64 编码后的用户数据
+    #[serde(rename = "UserData", skip_serializing_if = "Option::is_none")]
+    pub user_data: Option<String>,
+    #[serde(rename = "TagSpecification", default, skip_serializing_if = "Vec::is_empty")]
+    pub tag_specification: Vec<TcsTagSpecification>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsTagSpecification {
+    // instance / host / image / keypair
+    #[serde(rename = "ResourceType")]
+    pub resource_type: String,
+    #[serde(rename = "Tags")]
+    pub tags: Vec<TcsTag>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsPlacement {
+    #[serde(rename = "Zone")]
+    pub zone: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsDisk {
+    #[serde(rename = "DiskType")]
+    pub disk_type: String,
+    #[serde(rename = "DiskSize")]
+    pub disk_size: u16,
+    // 数据盘: 为 false 时退还实例后保留云硬盘
+    #[serde(rename = "DeleteWithInstance", skip_serializing_if = "Option::is_none")]
+    pub delete_with_instance: Option<bool>,
+    // 数据盘: 从快照创建
+    #[serde(rename = "SnapshotId", skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<String>,
+}
+
+impl TcsDisk {
+    pub fn new(disk_type: &str, disk_size: u16) -> Self {
+        TcsDisk {
+            disk_type: disk_type.to_string(),
+            disk_size,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsInternetAccessible {
+    #[serde(rename = "InternetChargeType")]
+    pub internet_charge_type: String,
+    #[serde(rename = "InternetMaxBandwidthOut")]
+    pub internet_max_bandwidth_out: u16,
+    #[serde(rename = "PublicIpAssigned")]
+    pub public_ip_assigned: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsInstanceMarketOptions {
+    #[serde(rename = "SpotOptions")]
+    pub spot_options: TcsSpotOptions,
+    #[serde(rename = "MarketType")]
+    pub market_type: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsSpotOptions {
+    #[serde(rename = "MaxPrice")]
+    pub max_price: String,
+    #[serde(rename = "SpotInstanceType")]
+    pub spot_instance_type: String,
+}
+
+// 登录设置: 密码 / 密钥 / 保留镜像原登录设置, 三选一
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct TcsLoginSettings {
+    #[serde(rename = "Password", skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(rename = "KeyIds", default, skip_serializing_if = "Vec::is_empty")]
+    pub key_ids: Vec<String>,
+    // TRUE / FALSE
+    #[serde(rename = "KeepImageLogin", skip_serializing_if = "Option::is_none")]
+    pub keep_image_login: Option<String>,
+}
+
+impl TcsLoginSettings {
+    // 优先使用密钥登录, 未配置密钥时使用密码
+    pub fn new(tcs_data: &TcsData) -> Self {
+        if !tcs_data.key_ids.is_empty() {
+            return TcsLoginSettings::key_ids(tcs_data.key_ids.clone());
+        }
+        TcsLoginSettings::password(tcs_data.password.as_str())
+    }
+
+    pub fn password(password: &str) -> Self {
+        TcsLoginSettings {
+            password: Some(password.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn key_ids(key_ids: Vec<String>) -> Self {
+        TcsLoginSettings { key_ids, ..Default::default() }
+    }
+
+    pub fn keep_image_login() -> Self {
+        TcsLoginSettings {
+            keep_image_login: Some("TRUE".to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+// 日志中隐藏登录密码
+impl std::fmt::Debug for TcsLoginSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcsLoginSettings")
+            .field("password", &self.password.as_ref().map(|_| "******"))
+            .field("key_ids", &self.key_ids)
+            .field("keep_image_login", &self.keep_image_login)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsEnhancedService {
+    #[serde(rename = "SecurityService")]
+    pub security_service: TcsRunService,
+    #[serde(rename = "MonitorService")]
+    pub monitor_service: TcsRunService,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsRunService {
+    #[serde(rename = "Enabled")]
+    pub enabled: bool,
+}
+
+impl TcsRunInstancesRequest {
+    // 按 TCS 配置数据生成默认请求参数(竞价实例)...
+    pub fn new(tcs_data: &TcsData, instance_type: &str) -> Self {
+        let tcs_info = &tcs_data.tcs_info;
+
+        TcsRunInstancesRequest {
+            placement: TcsPlacement { zone: tcs_data.tcs_zone.clone() },
+            instance_charge_type: tcs_info.instance_charge_type.clone(),
+            instance_type: instance_type.to_string(),
+            system_disk: TcsDisk::new("CLOUD_PREMIUM", 50),
+            data_disks: vec![TcsDisk::new("CLOUD_PREMIUM", 10)],
+            internet_accessible: TcsInternetAccessible {
+                internet_charge_type: "TRAFFIC_POSTPAID_BY_HOUR".to_string(),
+                internet_max_bandwidth_out: 10,
+                public_ip_assigned: true,
+            },
+            virtual_private_cloud: None,
+            security_group_ids: vec![],
+            instance_name: tcs_data.instance_name.clone(),
+            image_id: tcs_data.tcs_image_id.clone(),
+            instance_market_options: Some(TcsInstanceMarketOptions {
+                spot_options: TcsSpotOptions {
+                    max_price: format!("{}", tcs_info.max_unit_price),
+                    spot_instance_type: "one-time".to_string(),
+                },
+                market_type: "spot".to_string(),
+            }),
+            login_settings: TcsLoginSettings::new(tcs_data),
+            enhanced_service: TcsEnhancedService {
+                security_service: TcsRunService { enabled: false },
+                monitor_service: TcsRunService { enabled: false },
+            },
+            instance_count: 1,
+            user_data: None,
+            tag_specification: vec![],
+        }
+    }
+
+    // 设置实例标签
+    pub fn set_instance_tags(&mut self, tags: Vec<TcsTag>) {
+        self.tag_specification.retain(|tag_specification| tag_specification.resource_type != "instance");
+        if !tags.is_empty() {
+            self.tag_specification.push(TcsTagSpecification {
+                resource_type: "instance".to_string(),
+                tags,
+            });
+        }
+    }
+
+    // 指定私有网络与子网(子网须与实例位于同一可用区)
+    pub fn set_virtual_private_cloud(&mut self, vpc_id: &str, subnet_id: &str) {
+        self.virtual_private_cloud = Some(TcsVirtualPrivateCloud {
+            vpc_id: vpc_id.to_string(),
+            subnet_id: subnet_id.to_string(),
+            private_ip_addresses: None,
+        });
+    }
+
+    // 设置数据盘(为空时不创建数据盘)
+    pub fn set_data_disks(&mut self, data_disks: Vec<TcsDisk>) {
+        self.data_disks = data_disks;
+    }
+
+    // 绑定安全组(未指定时使用默认安全组)
+    pub fn set_security_group_ids(&mut self, security_group_ids: &[String]) {
+        self.security_group_ids = security_group_ids.to_vec();
+    }
+
+    // 设置用户数据(自动 Base64 编码)
+    pub fn set_user_data(&mut self, user_data: &[u8]) -> Result<(), Box<dyn Error>> {
+        self.user_data = Some(encode_user_data(user_data)?);
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseRunInstances {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "InstanceIdSet")]
+    pub instance_id_set: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseInquiryPrice {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "Price")]
+    pub price: TcsPrice,
+}
+
+// 询价结果: 磁盘费用已计入实例价格, 接口不单独返回
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsPrice {
+    #[serde(rename = "InstancePrice")]
+    pub instance_price: Option<TcsItemPrice>,
+    #[serde(rename = "BandwidthPrice")]
+    pub bandwidth_price: Option<TcsItemPrice>,
+}
+
+// 包年包月总价
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsTotalPrice {
+    #[serde(rename = "OriginalPrice")]
+    pub original_price: f32,
+    #[serde(rename = "DiscountPrice")]
+    pub discount_price: f32,
+    // 折扣, 例如 80.0 表示八折
+    #[serde(rename = "Discount")]
+    pub discount: f32,
+}
+
+// 按量计费返回单价(UnitPrice...), 包年包月返回总价(OriginalPrice...)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum TcsItemPrice {
+    Unit(TcsInstanceTypeQuotaPrice),
+    Total(TcsTotalPrice),
+}
+
+impl TcsItemPrice {
+    // 按量计费的折后单价与计费单位
+    pub fn discounted_unit_price(&self) -> Option<(&str, f32)> {
+        match self {
+            TcsItemPrice::Unit(price) => Some((price.charge_unit.as_str(), price.unit_price_discount)),
+            TcsItemPrice::Total(_) => None,
+        }
+    }
+
+    // 包年包月的折后总价
+    pub fn discounted_total_price(&self) -> Option<f32> {
+        match self {
+            TcsItemPrice::Unit(_) => None,
+            TcsItemPrice::Total(price) => Some(price.discount_price),
+        }
+    }
+
+    pub fn discount(&self) -> f32 {
+        match self {
+            TcsItemPrice::Unit(price) => price.discount,
+            TcsItemPrice::Total(price) => price.discount,
+        }
+    }
+}
+
+impl TencentCloudApi {
+    // 创建实例(指定请求参数)
+    pub fn tcs_run_instances_request(&mut self, tcs_region: &str, request: &TcsRunInstancesRequest) -> Result<Vec<String>, Box<dyn Error>> {
+        info!("[######][创建实例][@][tcs_run_instances_request()][tcs_region: {}][instance_name: {}]", tcs_region, request.instance_name);
+
+        let tcs_response_data: TcsResponseRunInstances = self.tcs_request_action("RunInstances", tcs_region, &to_value(request)?)?;
+        Ok(tcs_response_data.instance_id_set)
+    }
+
+    // 创建实例询价
+    // [创建实例询价](https://cloud.tencent.com/document/api/213/15726)
+    pub fn tcs_inquiry_price_run_instances(&mut self, tcs_region: &str, request: &TcsRunInstancesRequest) -> Result<TcsPrice, Box<dyn Error>> {
+        info!("[######][创建实例询价][@][tcs_inquiry_price_run_instances()][tcs_region: {}][instance_type: {}]", tcs_region, request.instance_type);
+
+        let tcs_response_data: TcsResponseInquiryPrice = self.tcs_request_action("InquiryPriceRunInstances", tcs_region, &to_value(request)?)?;
+        info!("[tcs_response_data: {:?}]", tcs_response_data);
+
+        Ok(tcs_response_data.price)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_inquiry_price() {
+        let content = r#"{"Price": {"InstancePrice": {"UnitPrice": 0.52, "ChargeUnit": "HOUR", "UnitPriceDiscount": 0.11, "Discount": 20.0}, "BandwidthPrice": {"UnitPrice": 0.8, "ChargeUnit": "GB", "UnitPriceDiscount": 0.8, "Discount": 100.0}}, "RequestId": "b1a0c6f7"}"#;
+        let response: TcsResponseInquiryPrice = serde_json::from_str(content).unwrap();
+
+        let instance_price = response.price.instance_price.unwrap();
+        assert_eq!(instance_price.discounted_unit_price(), Some(("HOUR", 0.11)));
+        assert_eq!(instance_price.discount(), 20.0);
+
+        // 包年包月只返回总价
+        let content = r#"{"InstancePrice": {"OriginalPrice": 300.0, "DiscountPrice": 240.0, "Discount": 80.0}}"#;
+        let price: TcsPrice = serde_json::from_str(content).unwrap();
+
+        let instance_price = price.instance_price.unwrap();
+        assert!(instance_price.discounted_unit_price().is_none());
+        assert_eq!(instance_price.discounted_total_price(), Some(240.0));
+        assert_eq!(instance_price.discount(), 80.0);
+        assert!(price.bandwidth_price.is_none());
+    }
+}
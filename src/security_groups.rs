@@ -0,0 +1,421 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::error::Error;
+
+use crate::{TCS_SERVICE_VPC, TcsFilter, TcsResponseEmpty, TcsTag, TencentCloudApi};
+
+// 安全组
+// [查看安全组](https://cloud.tencent.com/document/api/215/15808)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsSecurityGroup {
+    #[serde(rename = "SecurityGroupId")]
+    pub security_group_id: String,
+    #[serde(rename = "SecurityGroupName")]
+    pub security_group_name: String,
+    #[serde(rename = "SecurityGroupDesc")]
+    pub security_group_desc: String,
+    #[serde(rename = "ProjectId")]
+    pub project_id: Option<String>,
+    #[serde(rename = "IsDefault")]
+    pub is_default: Option<bool>,
+    #[serde(rename = "CreatedTime")]
+    pub created_time: Option<String>,
+    #[serde(rename = "TagSet", default, deserialize_with = "crate::null_default")]
+    pub tag_set: Vec<TcsTag>,
+}
+
+// 安全组规则, 来源/目的为 CidrBlock / Ipv6CidrBlock / SecurityGroupId 之一
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsSecurityGroupPolicy {
+    #[serde(rename = "PolicyIndex", skip_serializing_if = "Option::is_none")]
+    pub policy_index: Option<i64>,
+    // TCP / UDP / ICMP / ICMPv6 / ALL
+    #[serde(rename = "Protocol", skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    // ALL / 22 / 80,443 / 8000-8010
+    #[serde(rename = "Port", skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    #[serde(rename = "CidrBlock", skip_serializing_if = "Option::is_none")]
+    pub cidr_block: Option<String>,
+    #[serde(rename = "Ipv6CidrBlock", skip_serializing_if = "Option::is_none")]
+    pub ipv6_cidr_block: Option<String>,
+    #[serde(rename = "SecurityGroupId", skip_serializing_if = "Option::is_none")]
+    pub security_group_id: Option<String>,
+    // ACCEPT / DROP
+    #[serde(rename = "Action")]
+    pub action: String,
+    #[serde(rename = "PolicyDescription", skip_serializing_if = "Option::is_none")]
+    pub policy_description: Option<String>,
+    #[serde(rename = "ModifyTime", skip_serializing_if = "Option::is_none")]
+    pub modify_time: Option<String>,
+}
+
+impl TcsSecurityGroupPolicy {
+    pub fn new(protocol: &str, port: &str, cidr_block: &str, action: &str) -> Self {
+        TcsSecurityGroupPolicy {
+            protocol: Some(protocol.to_string()),
+            port: Some(port.to_string()),
+            cidr_block: Some(cidr_block.to_string()),
+            action: action.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_description(mut self, policy_description: &str) -> Self {
+        self.policy_description = Some(policy_description.to_string());
+        self
+    }
+
+    // 比较规则内容(忽略索引/描述/修改时间, 大小写不敏感)
+    pub fn policy_key(&self) -> String {
+        let protocol = self.protocol.as_deref().unwrap_or("ALL").to_uppercase();
+        let port = match protocol.as_str() {
+            "ALL" | "ICMP" | "ICMPV6" => "ALL".to_string(),
+            _ => self.port.as_deref().unwrap_or("ALL").replace(' ', "").to_uppercase(),
+        };
+        let peer = [&self.cidr_block, &self.ipv6_cidr_block, &self.security_group_id].into_iter().flatten().find(|peer| !peer.is_empty()).cloned().unwrap_or_default();
+
+        format!("{}|{}|{}|{}", protocol, port, peer, self.action.to_uppercase())
+    }
+
+    // 用于创建/删除的规则内容
+    fn content(&self) -> Self {
+        TcsSecurityGroupPolicy {
+            policy_index: None,
+            modify_time: None,
+            ..self.clone()
+        }
+    }
+}
+
+// 出入站规则集合
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsSecurityGroupPolicySet {
+    #[serde(rename = "Version", skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(rename = "Ingress", default, skip_serializing_if = "Vec::is_empty")]
+    pub ingress: Vec<TcsSecurityGroupPolicy>,
+    #[serde(rename = "Egress", default, skip_serializing_if = "Vec::is_empty")]
+    pub egress: Vec<TcsSecurityGroupPolicy>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeSecurityGroups {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "SecurityGroupSet")]
+    pub security_group_set: Vec<TcsSecurityGroup>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateSecurityGroup {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "SecurityGroup")]
+    pub security_group: TcsSecurityGroup,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeSecurityGroupPolicies {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "SecurityGroupPolicySet")]
+    pub security_group_policy_set: TcsSecurityGroupPolicySet,
+}
+
+// 单个方向的规则变更
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsPolicyDirectionDiff {
+    // (原规则, 新规则) 成对替换, 保留原规则位置
+    pub replace: Vec<(TcsSecurityGroupPolicy, TcsSecurityGroupPolicy)>,
+    pub create: Vec<TcsSecurityGroupPolicy>,
+    pub delete: Vec<TcsSecurityGroupPolicy>,
+}
+
+impl TcsPolicyDirectionDiff {
+    pub fn is_empty(&self) -> bool {
+        self.replace.is_empty() && self.create.is_empty() && self.delete.is_empty()
+    }
+}
+
+// 安全组规则变更
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsSecurityGroupPolicyDiff {
+    pub ingress: TcsPolicyDirectionDiff,
+    pub egress: TcsPolicyDirectionDiff,
+    // 含 DROP 规则且顺序变化时需整体覆盖(规则按顺序匹配)
+    pub overwrite: bool,
+}
+
+impl TcsSecurityGroupPolicyDiff {
+    pub fn is_empty(&self) -> bool {
+        !self.overwrite && self.ingress.is_empty() && self.egress.is_empty()
+    }
+}
+
+// 对比单个方向的规则
+fn diff_policy_direction(live: &[TcsSecurityGroupPolicy], desired: &[TcsSecurityGroupPolicy]) -> (TcsPolicyDirectionDiff, bool) {
+    let live_keys: Vec<String> = live.iter().map(|policy| policy.policy_key()).collect();
+    let desired_keys: Vec<String> = desired.iter().map(|policy| policy.policy_key()).collect();
+
+    let mut delete: Vec<TcsSecurityGroupPolicy> = live.iter().filter(|policy| !desired_keys.contains(&policy.policy_key())).cloned().collect();
+    let mut create: Vec<TcsSecurityGroupPolicy> = desired.iter().filter(|policy| !live_keys.contains(&policy.policy_key())).cloned().collect();
+
+    // 保留规则的相对顺序是否一致
+    let kept_live: Vec<&String> = live_keys.iter().filter(|key| desired_keys.contains(key)).collect();
+    let kept_desired: Vec<&String> = desired_keys.iter().filter(|key| live_keys.contains(key)).collect();
+    let has_drop = desired.iter().chain(live.iter()).any(|policy| policy.action.eq_ignore_ascii_case("DROP"));
+    let reordered = has_drop && (kept_live != kept_desired || !create.is_empty());
+
+    // 删除与新增成对时改为替换, 减少调用次数
+    let pairs = delete.len().min(create.len());
+    let delete_rest = delete.split_off(pairs);
+    let create_rest = create.split_off(pairs);
+    let replace = delete.into_iter().zip(create).collect();
+
+    (TcsPolicyDirectionDiff { replace, create: create_rest, delete: delete_rest }, reordered)
+}
+
+// 对比现有规则与期望规则
+pub fn diff_security_group_policies(live: &TcsSecurityGroupPolicySet, desired: &TcsSecurityGroupPolicySet) -> TcsSecurityGroupPolicyDiff {
+    let (ingress, ingress_reordered) = diff_policy_direction(&live.ingress, &desired.ingress);
+    let (egress, egress_reordered) = diff_policy_direction(&live.egress, &desired.egress);
+
+    TcsSecurityGroupPolicyDiff {
+        ingress,
+        egress,
+        overwrite: ingress_reordered || egress_reordered,
+    }
+}
+
+impl TencentCloudApi {
+    // 查询安全组列表
+    pub fn tcs_describe_security_groups(&mut self, tcs_region: &str, security_group_ids: &[String], filters: &[TcsFilter]) -> Result<Vec<TcsSecurityGroup>, Box<dyn Error>> {
+        info!(
+            "[######][查询安全组列表][@][tcs_describe_security_groups()][tcs_region: {}][security_group_ids: {:?}][filters: {:?}]",
+            tcs_region, security_group_ids, filters
+        );
+
+        let limit = 100;
+        let mut security_group_set: Vec<TcsSecurityGroup> = vec![];
+        loop {
+            // 私有网络接口的 Offset/Limit 为字符串类型
+            let mut payload = json!({
+                "Offset": security_group_set.len().to_string(),
+                "Limit": limit.to_string()
+            });
+            if !security_group_ids.is_empty() {
+                payload["SecurityGroupIds"] = json!(security_group_ids);
+            } else if !filters.is_empty() {
+                payload["Filters"] = json!(filters);
+            }
+
+            let tcs_response_data: TcsResponseDescribeSecurityGroups = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DescribeSecurityGroups", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.security_group_set.len();
+            security_group_set.extend(tcs_response_data.security_group_set);
+
+            if page_count == 0 || security_group_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(security_group_set)
+    }
+
+    // 创建安全组
+    pub fn tcs_create_security_group(&mut self, tcs_region: &str, group_name: &str, group_description: &str, project_id: i64, tags: &[TcsTag]) -> Result<TcsSecurityGroup, Box<dyn Error>> {
+        info!("[######][创建安全组][@][tcs_create_security_group()][tcs_region: {}][group_name: {}][project_id: {}]", tcs_region, group_name, project_id);
+
+        let payload = json!({
+            "GroupName": group_name,
+            "GroupDescription": group_description,
+            "ProjectId": project_id.to_string(),
+            "Tags": tags
+        });
+
+        let tcs_response_data: TcsResponseCreateSecurityGroup = self.tcs_request_service_action(&TCS_SERVICE_VPC, "CreateSecurityGroup", tcs_region, &payload)?;
+        Ok(tcs_response_data.security_group)
+    }
+
+    // 删除安全组(被实例或其他安全组规则引用时无法删除)
+    pub fn tcs_delete_security_group(&mut self, tcs_region: &str, security_group_id: &str) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除安全组][@][tcs_delete_security_group()][tcs_region: {}][security_group_id: {}]", tcs_region, security_group_id);
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DeleteSecurityGroup", tcs_region, &json!({ "SecurityGroupId": security_group_id }))?;
+        Ok(())
+    }
+
+    // 查询安全组规则
+    pub fn tcs_describe_security_group_policies(&mut self, tcs_region: &str, security_group_id: &str) -> Result<TcsSecurityGroupPolicySet, Box<dyn Error>> {
+        info!("[######][查询安全组规则][@][tcs_describe_security_group_policies()][tcs_region: {}][security_group_id: {}]", tcs_region, security_group_id);
+
+        let payload = json!({ "SecurityGroupId": security_group_id });
+        let tcs_response_data: TcsResponseDescribeSecurityGroupPolicies = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DescribeSecurityGroupPolicies", tcs_region, &payload)?;
+        Ok(tcs_response_data.security_group_policy_set)
+    }
+
+    // 添加安全组规则(单次只能添加一个方向的规则)
+    pub fn tcs_create_security_group_policies(&mut self, tcs_region: &str, security_group_id: &str, policy_set: &TcsSecurityGroupPolicySet) -> Result<(), Box<dyn Error>> {
+        info!("[######][添加安全组规则][@][tcs_create_security_group_policies()][tcs_region: {}][security_group_id: {}][policy_set: {:?}]", tcs_region, security_group_id, policy_set);
+
+        let payload = json!({
+            "SecurityGroupId": security_group_id,
+            "SecurityGroupPolicySet": policy_set
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_VPC, "CreateSecurityGroupPolicies", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 替换安全组规则(单次只能替换一个方向的规则, 新旧规则按顺序一一对应)
+    pub fn tcs_replace_security_group_policies(
+        &mut self,
+        tcs_region: &str,
+        security_group_id: &str,
+        original_policy_set: &TcsSecurityGroupPolicySet,
+        policy_set: &TcsSecurityGroupPolicySet,
+    ) -> Result<(), Box<dyn Error>> {
+        info!(
+            "[######][替换安全组规则][@][tcs_replace_security_group_policies()][tcs_region: {}][security_group_id: {}][original_policy_set: {:?}][policy_set: {:?}]",
+            tcs_region, security_group_id, original_policy_set, policy_set
+        );
+
+        let payload = json!({
+            "SecurityGroupId": security_group_id,
+            "OriginalSecurityGroupPolicySet": original_policy_set,
+            "SecurityGroupPolicySet": policy_set
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_VPC, "ReplaceSecurityGroupPolicies", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 删除安全组规则(按规则内容删除, 单次只能删除一个方向的规则)
+    pub fn tcs_delete_security_group_policies(&mut self, tcs_region: &str, security_group_id: &str, policy_set: &TcsSecurityGroupPolicySet) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除安全组规则][@][tcs_delete_security_group_policies()][tcs_region: {}][security_group_id: {}][policy_set: {:?}]", tcs_region, security_group_id, policy_set);
+
+        let payload = json!({
+            "SecurityGroupId": security_group_id,
+            "SecurityGroupPolicySet": policy_set
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DeleteSecurityGroupPolicies", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 覆盖安全组全部出入站规则(保留期望规则的顺序)
+    pub fn tcs_modify_security_group_policies(&mut self, tcs_region: &str, security_group_id: &str, policy_set: &TcsSecurityGroupPolicySet) -> Result<(), Box<dyn Error>> {
+        info!("[######][覆盖安全组规则][@][tcs_modify_security_group_policies()][tcs_region: {}][security_group_id: {}][policy_set: {:?}]", tcs_region, security_group_id, policy_set);
+
+        let payload = json!({
+            "SecurityGroupId": security_group_id,
+            "SecurityGroupPolicySet": policy_set
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_VPC, "ModifySecurityGroupPolicies", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 按期望规则同步安全组, 只提交有差异的规则
+    pub fn tcs_sync_security_group_policies(&mut self, tcs_region: &str, security_group_id: &str, desired: &TcsSecurityGroupPolicySet) -> Result<TcsSecurityGroupPolicyDiff, Box<dyn Error>> {
+        info!("[######][同步安全组规则][@][tcs_sync_security_group_policies()][tcs_region: {}][security_group_id: {}]", tcs_region, security_group_id);
+
+        let live = self.tcs_describe_security_group_policies(tcs_region, security_group_id)?;
+        let diff = diff_security_group_policies(&live, desired);
+        if diff.is_empty() {
+            info!("[安全组规则无变化][security_group_id: {}]", security_group_id);
+            return Ok(diff);
+        }
+
+        if diff.overwrite {
+            let policy_set = TcsSecurityGroupPolicySet {
+                version: live.version.clone(),
+                ingress: desired.ingress.iter().map(|policy| policy.content()).collect(),
+                egress: desired.egress.iter().map(|policy| policy.content()).collect(),
+            };
+            self.tcs_modify_security_group_policies(tcs_region, security_group_id, &policy_set)?;
+            return Ok(diff);
+        }
+
+        // 按方向依次提交: 替换 -> 删除 -> 新增
+        for (ingress, direction) in [(true, &diff.ingress), (false, &diff.egress)] {
+            let policy_set = |policies: Vec<TcsSecurityGroupPolicy>| {
+                let mut policy_set = TcsSecurityGroupPolicySet::default();
+                if ingress {
+                    policy_set.ingress = policies;
+                } else {
+                    policy_set.egress = policies;
+                }
+                policy_set
+            };
+
+            if !direction.replace.is_empty() {
+                let original = policy_set(direction.replace.iter().map(|(old_policy, _)| old_policy.content()).collect());
+                let replaced = policy_set(direction.replace.iter().map(|(_, new_policy)| new_policy.content()).collect());
+                self.tcs_replace_security_group_policies(tcs_region, security_group_id, &original, &replaced)?;
+            }
+            if !direction.delete.is_empty() {
+                let deleted = policy_set(direction.delete.iter().map(|policy| policy.content()).collect());
+                self.tcs_delete_security_group_policies(tcs_region, security_group_id, &deleted)?;
+            }
+            if !direction.create.is_empty() {
+                let created = policy_set(direction.create.iter().map(|policy| policy.content()).collect());
+                self.tcs_create_security_group_policies(tcs_region, security_group_id, &created)?;
+            }
+        }
+
+        Ok(diff)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn diff_policies() {
+        let live = TcsSecurityGroupPolicySet {
+            version: Some("3".to_string()),
+            ingress: vec![
+                TcsSecurityGroupPolicy::new("tcp", "22", "0.0.0.0/0", "ACCEPT"),
+                TcsSecurityGroupPolicy::new("TCP", "80", "0.0.0.0/0", "ACCEPT"),
+                TcsSecurityGroupPolicy::new("TCP", "3306", "0.0.0.0/0", "ACCEPT"),
+                TcsSecurityGroupPolicy::new("UDP", "53", "10.0.0.0/8", "ACCEPT"),
+            ],
+            egress: vec![TcsSecurityGroupPolicy::new("ALL", "ALL", "0.0.0.0/0", "ACCEPT")],
+        };
+        let desired = TcsSecurityGroupPolicySet {
+            version: None,
+            ingress: vec![
+                TcsSecurityGroupPolicy::new("TCP", "22", "0.0.0.0/0", "ACCEPT").with_description("ssh"),
+                TcsSecurityGroupPolicy::new("TCP", "80", "0.0.0.0/0", "ACCEPT"),
+                TcsSecurityGroupPolicy::new("TCP", "443", "0.0.0.0/0", "ACCEPT"),
+            ],
+            egress: vec![TcsSecurityGroupPolicy::new("ALL", "", "0.0.0.0/0", "accept")],
+        };
+
+        let diff = diff_security_group_policies(&live, &desired);
+        assert!(!diff.overwrite);
+        assert!(diff.egress.is_empty());
+        assert_eq!(diff.ingress.replace.len(), 1);
+        assert_eq!(diff.ingress.replace[0].0.port.as_deref(), Some("3306"));
+        assert_eq!(diff.ingress.replace[0].1.port.as_deref(), Some("443"));
+        assert!(diff.ingress.create.is_empty());
+        assert_eq!(diff.ingress.delete.len(), 1);
+        assert_eq!(diff.ingress.delete[0].port.as_deref(), Some("53"));
+
+        // 含 DROP 规则且顺序变化时整体覆盖
+        let mut reordered = live.clone();
+        reordered.ingress.push(TcsSecurityGroupPolicy::new("ALL", "ALL", "0.0.0.0/0", "DROP"));
+        let mut desired = reordered.clone();
+        desired.ingress.swap(0, 1);
+        assert!(diff_security_group_policies(&reordered, &desired).overwrite);
+        assert!(diff_security_group_policies(&reordered, &reordered).is_empty());
+    }
+}
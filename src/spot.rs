@@ -0,0 +1,249 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+use crate::{TcsData, TcsFilter, TcsInstanceInfo, TcsMetadataClient, TcsRunInstancesRequest, TencentCloudApi};
+
+// 竞价实例事件
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum SpotEvent {
+    // 即将被回收(来自实例元数据)
+    Interrupting { instance_id: String, termination_time: String },
+    // 已被回收
+    Reclaimed { instance_id: String, instance_name: String },
+    // 已创建替换实例
+    Replaced { instance_id: String, instance_name: String, replacement_ids: Vec<String> },
+    // 创建替换实例失败, 下次轮询时重试
+    ReplaceFailed { instance_id: String, instance_name: String, error: String },
+}
+
+// 回收后不可恢复的实例状态
+const TCS_SPOT_RECLAIMED_STATES: [&str; 2] = ["SHUTDOWN", "TERMINATING"];
+
+// 对比前后两次查询结果, 找出被回收的实例(通过接口主动退还的实例除外)
+// 只有查询到回收状态, 或收到本实例的回收通知(interrupted_id)才认为已回收, 消失的实例只记录日志
+pub fn detect_reclaimed_instances(known: &HashMap<String, TcsInstanceInfo>, instance_set: &[TcsInstanceInfo], interrupted_id: Option<&str>) -> Vec<TcsInstanceInfo> {
+    let mut reclaimed = vec![];
+    for (instance_id, known_instance) in known {
+        let current = instance_set.iter().find(|instance| &instance.instance_id == instance_id);
+        let latest = current.unwrap_or(known_instance);
+        if latest.latest_operation.as_deref() == Some("TerminateInstances") {
+            continue;
+        }
+
+        match current {
+            None if interrupted_id == Some(instance_id.as_str()) => reclaimed.push(known_instance.clone()),
+            None => warn!("[竞价实例已消失, 未查询到回收状态, 不做替换][instance_id: {}][instance_name: {}]", instance_id, known_instance.instance_name),
+            Some(instance) if TCS_SPOT_RECLAIMED_STATES.contains(&instance.instance_state.as_str()) => reclaimed.push(instance.clone()),
+            _ => {}
+        }
+    }
+    reclaimed.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+    reclaimed
+}
+
+// 竞价实例回收监控: 轮询 DescribeInstances, 可选自动创建替换实例
+// 在竞价实例内部运行时可同时检查实例元数据中的回收通知
+pub struct SpotWatcher {
+    pub tcs_region: String,
+    pub filters: Vec<TcsFilter>,
+    // 替换实例的配置模板(密码/密钥/价格上限等), 为空时不自动替换
+    pub replacement: Option<TcsData>,
+    metadata: Option<TcsMetadataClient>,
+    // 已收到回收通知的本实例ID
+    interrupted_id: Option<String>,
+    known: HashMap<String, TcsInstanceInfo>,
+    // 替换失败, 等待重试的实例
+    pending: HashMap<String, TcsInstanceInfo>,
+}
+
+impl SpotWatcher {
+    pub fn new(tcs_region: &str) -> Self {
+        SpotWatcher {
+            tcs_region: tcs_region.to_string(),
+            filters: vec![TcsFilter::new("instance-charge-type", &["SPOTPAID"])],
+            replacement: None,
+            metadata: None,
+            interrupted_id: None,
+            known: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn with_replacement(mut self, tcs_data: TcsData) -> Self {
+        self.replacement = Some(tcs_data);
+        self
+    }
+
+    // 每次轮询时检查本实例的回收通知(spot/termination-time)
+    pub fn with_metadata(mut self, metadata: TcsMetadataClient) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    // 轮询一次, 返回本次发现的事件(单个实例替换失败不影响其他实例)
+    pub fn poll(&mut self, api: &mut TencentCloudApi) -> Result<Vec<SpotEvent>, Box<dyn Error>> {
+        let mut events = vec![];
+
+        // 本实例回收通知只上报一次...
+        if self.interrupted_id.is_none()
+            && let Some(metadata) = &self.metadata
+        {
+            match check_spot_termination(metadata) {
+                Result::Ok(Some(event)) => {
+                    if let SpotEvent::Interrupting { instance_id, .. } = &event {
+                        self.interrupted_id = Some(instance_id.clone());
+                    }
+                    events.push(event);
+                }
+                Result::Ok(None) => {}
+                Result::Err(err) => warn!("[读取回收通知失败][err: {}]", err),
+            }
+        }
+
+        let instance_set = api.tcs_describe_instances_by_filters(self.tcs_region.as_str(), &self.filters)?;
+        let reclaimed = detect_reclaimed_instances(&self.known, &instance_set, self.interrupted_id.as_deref());
+
+        // 先重试上次替换失败的实例
+        let mut pending: Vec<TcsInstanceInfo> = self.pending.drain().map(|(_, instance)| instance).collect();
+        pending.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+        for instance in pending {
+            self.handle_reclaimed(api, instance, &mut events);
+        }
+
+        for instance in reclaimed {
+            warn!("[竞价实例已回收][instance_id: {}][instance_name: {}]", instance.instance_id, instance.instance_name);
+            events.push(SpotEvent::Reclaimed {
+                instance_id: instance.instance_id.clone(),
+                instance_name: instance.instance_name.clone(),
+            });
+            self.handle_reclaimed(api, instance, &mut events);
+        }
+
+        // 所有回收实例处理完成后再更新已知实例
+        self.known = instance_set
+            .into_iter()
+            .filter(|instance| !TCS_SPOT_RECLAIMED_STATES.contains(&instance.instance_state.as_str()))
+            .map(|instance| (instance.instance_id.clone(), instance))
+            .collect();
+
+        Ok(events)
+    }
+
+    fn handle_reclaimed(&mut self, api: &mut TencentCloudApi, instance: TcsInstanceInfo, events: &mut Vec<SpotEvent>) {
+        if self.replacement.is_none() {
+            return;
+        }
+
+        match self.replace(api, &instance) {
+            Result::Ok(replacement_ids) => events.push(SpotEvent::Replaced {
+                instance_id: instance.instance_id.clone(),
+                instance_name: instance.instance_name.clone(),
+                replacement_ids,
+            }),
+            Result::Err(err) => {
+                error!("[创建替换实例失败][instance_id: {}][err: {}]", instance.instance_id, err);
+                events.push(SpotEvent::ReplaceFailed {
+                    instance_id: instance.instance_id.clone(),
+                    instance_name: instance.instance_name.clone(),
+                    error: err.to_string(),
+                });
+                self.pending.insert(instance.instance_id.clone(), instance);
+            }
+        }
+    }
+
+    // 按被回收实例的规格重新挑选竞价机型并创建
+    fn replace(&self, api: &mut TencentCloudApi, instance: &TcsInstanceInfo) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut tcs_data = self.replacement.clone().ok_or("未配置替换实例!")?;
+        tcs_data.tcs_region = self.tcs_region.clone();
+        tcs_data.instance_name = instance.instance_name.clone();
+        tcs_data.tcs_info.instance_cpu = instance.cpu;
+        tcs_data.tcs_info.instance_memory = instance.memory;
+        if let Some(placement) = &instance.placement {
+            tcs_data.tcs_zone = placement.zone.clone();
+        }
+        if let Some(image_id) = &instance.image_id {
+            tcs_data.tcs_image_id = image_id.clone();
+        }
+
+        // 挑选机型 - 可用机型列表...
+        let tcs_instance_info = api.tcs_get_zone_instance_info(&tcs_data)?;
+        let request = TcsRunInstancesRequest::new(&tcs_data, tcs_instance_info.instance_type.as_str());
+
+        api.tcs_run_instances_request(self.tcs_region.as_str(), &request)
+    }
+
+    // 持续监控, on_event 返回 false 时停止
+    pub fn watch<F>(&mut self, api: &mut TencentCloudApi, interval: Duration, mut on_event: F) -> Result<(), Box<dyn Error>>
+    where
+        F: FnMut(&SpotEvent) -> bool,
+    {
+        loop {
+            for event in self.poll(api)? {
+                if !on_event(&event) {
+                    return Ok(());
+                }
+            }
+            thread::sleep(interval);
+        }
+    }
+}
+
+// 在竞价实例内部检查回收通知
+pub fn check_spot_termination(metadata: &TcsMetadataClient) -> Result<Option<SpotEvent>, Box<dyn Error>> {
+    let termination_time = match metadata.spot_termination_time()? {
+        Some(termination_time) => termination_time,
+        None => return Ok(None),
+    };
+
+    let instance_id = metadata.instance_id()?;
+    warn!("[竞价实例即将回收][instance_id: {}][termination_time: {}]", instance_id, termination_time);
+
+    Ok(Some(SpotEvent::Interrupting { instance_id, termination_time }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::instance_info;
+    use serde_json::json;
+
+    fn instance(instance_id: &str, instance_state: &str, latest_operation: Option<&str>) -> TcsInstanceInfo {
+        instance_info(json!({
+            "InstanceId": instance_id,
+            "InstanceName": format!("spot-{}", instance_id),
+            "InstanceState": instance_state,
+            "LatestOperation": latest_operation
+        }))
+    }
+
+    #[test]
+    fn detect_reclaimed() {
+        let known: HashMap<String, TcsInstanceInfo> = vec![
+            instance("ins-1", "RUNNING", None),
+            instance("ins-2", "RUNNING", None),
+            instance("ins-3", "RUNNING", None),
+            instance("ins-4", "RUNNING", Some("TerminateInstances")),
+        ]
+        .into_iter()
+        .map(|instance| (instance.instance_id.clone(), instance))
+        .collect();
+
+        // ins-2 消失, ins-3 被关闭, ins-4 为主动退还
+        let instance_set = vec![instance("ins-1", "RUNNING", None), instance("ins-3", "SHUTDOWN", None), instance("ins-5", "PENDING", None)];
+
+        let reclaimed = detect_reclaimed_instances(&known, &instance_set, None);
+        let reclaimed_ids: Vec<&str> = reclaimed.iter().map(|instance| instance.instance_id.as_str()).collect();
+        assert_eq!(reclaimed_ids, vec!["ins-3"]);
+
+        // 收到回收通知的实例消失后才认为已回收
+        let reclaimed = detect_reclaimed_instances(&known, &instance_set, Some("ins-2"));
+        let reclaimed_ids: Vec<&str> = reclaimed.iter().map(|instance| instance.instance_id.as_str()).collect();
+        assert_eq!(reclaimed_ids, vec!["ins-2", "ins-3"]);
+    }
+}
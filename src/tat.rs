@@ -0,0 +1,311 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::error::Error;
+
+use crate::{TCS_SERVICE_TAT, TcsFilter, TcsWaiter, TencentCloudApi};
+
+// 命令执行参数
+// [执行命令](https://cloud.tencent.com/document/api/1340/52680)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsRunCommandRequest {
+    // Base64 编码后的命令内容
+    #[serde(rename = "Content")]
+    pub content: String,
+    #[serde(rename = "InstanceIds")]
+    pub instance_ids: Vec<String>,
+    // SHELL / POWERSHELL / BAT
+    #[serde(rename = "CommandType")]
+    pub command_type: String,
+    #[serde(rename = "WorkingDirectory", skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<String>,
+    // 秒
+    #[serde(rename = "Timeout")]
+    pub timeout: u64,
+    #[serde(rename = "Username", skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(rename = "CommandName", skip_serializing_if = "Option::is_none")]
+    pub command_name: Option<String>,
+    // 是否保存为命令, 保存后可通过 InvokeCommand 重复执行
+    #[serde(rename = "SaveCommand")]
+    pub save_command: bool,
+}
+
+impl TcsRunCommandRequest {
+    // Shell 脚本, 默认 root 用户执行, 超时 60 秒
+    pub fn shell(instance_ids: &[String], script: &str) -> Self {
+        TcsRunCommandRequest {
+            content: STANDARD.encode(script),
+            instance_ids: instance_ids.to_vec(),
+            command_type: "SHELL".to_string(),
+            working_directory: None,
+            timeout: 60,
+            username: None,
+            command_name: None,
+            save_command: false,
+        }
+    }
+}
+
+// 执行活动
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsInvocation {
+    #[serde(rename = "InvocationId")]
+    pub invocation_id: String,
+    #[serde(rename = "CommandId")]
+    pub command_id: String,
+    // PENDING / RUNNING / CANCELLING / SUCCESS / FAILED / TIMEOUT / PARTIAL_FAILED / PARTIAL_CANCELLED / CANCELLED ...
+    #[serde(rename = "InvocationStatus")]
+    pub invocation_status: String,
+    #[serde(rename = "InvocationTaskBasicInfoSet", default, deserialize_with = "crate::null_default")]
+    pub invocation_task_basic_info_set: Vec<TcsInvocationTaskBasicInfo>,
+    #[serde(rename = "CreatedTime")]
+    pub created_time: Option<String>,
+    #[serde(rename = "UpdatedTime")]
+    pub updated_time: Option<String>,
+}
+
+impl TcsInvocation {
+    // 执行活动是否已结束
+    pub fn is_finished(&self) -> bool {
+        !matches!(self.invocation_status.as_str(), "PENDING" | "RUNNING" | "CANCELLING")
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsInvocationTaskBasicInfo {
+    #[serde(rename = "InvocationTaskId")]
+    pub invocation_task_id: String,
+    #[serde(rename = "TaskStatus")]
+    pub task_status: String,
+    #[serde(rename = "InstanceId")]
+    pub instance_id: String,
+}
+
+// 执行任务(单个实例上的执行)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsInvocationTask {
+    #[serde(rename = "InvocationId")]
+    pub invocation_id: String,
+    #[serde(rename = "InvocationTaskId")]
+    pub invocation_task_id: String,
+    #[serde(rename = "CommandId")]
+    pub command_id: String,
+    #[serde(rename = "InstanceId")]
+    pub instance_id: String,
+    // PENDING / DELIVERING / DELIVER_DELAYED / DELIVER_FAILED / START_FAILED / RUNNING / SUCCESS / FAILED / TIMEOUT / TASK_TIMEOUT / CANCELLING / CANCELLED / TERMINATED
+    #[serde(rename = "TaskStatus")]
+    pub task_status: String,
+    #[serde(rename = "TaskResult")]
+    pub task_result: Option<TcsTaskResult>,
+    #[serde(rename = "ErrorInfo")]
+    pub error_info: Option<String>,
+    #[serde(rename = "StartTime")]
+    pub start_time: Option<String>,
+    #[serde(rename = "EndTime")]
+    pub end_time: Option<String>,
+}
+
+impl TcsInvocationTask {
+    pub fn exit_code(&self) -> Option<i64> {
+        self.task_result.as_ref().map(|task_result| task_result.exit_code)
+    }
+
+    // 解码后的输出(最多 24KB, 超出部分被丢弃)
+    pub fn output(&self) -> Result<String, Box<dyn Error>> {
+        match &self.task_result {
+            Some(task_result) => task_result.output_text(),
+            None => Ok(String::new()),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.task_status == "SUCCESS" && self.exit_code() == Some(0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsTaskResult {
+    #[serde(rename = "ExitCode")]
+    pub exit_code: i64,
+    // Base64 编码
+    #[serde(rename = "Output")]
+    pub output: String,
+    // 被丢弃的输出长度
+    #[serde(rename = "Dropped")]
+    pub dropped: Option<u64>,
+    #[serde(rename = "ExecStartTime")]
+    pub exec_start_time: Option<String>,
+    #[serde(rename = "ExecEndTime")]
+    pub exec_end_time: Option<String>,
+}
+
+impl TcsTaskResult {
+    pub fn output_text(&self) -> Result<String, Box<dyn Error>> {
+        let output = STANDARD.decode(self.output.as_bytes())?;
+        Ok(String::from_utf8_lossy(&output).to_string())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseRunCommand {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "CommandId")]
+    pub command_id: String,
+    #[serde(rename = "InvocationId")]
+    pub invocation_id: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseInvokeCommand {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "InvocationId")]
+    pub invocation_id: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeInvocations {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "InvocationSet")]
+    pub invocation_set: Vec<TcsInvocation>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeInvocationTasks {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "InvocationTaskSet")]
+    pub invocation_task_set: Vec<TcsInvocationTask>,
+}
+
+impl TencentCloudApi {
+    // 执行命令(实例需安装自动化助手), 返回 (命令ID, 执行活动ID)
+    pub fn tcs_run_command(&mut self, tcs_region: &str, request: &TcsRunCommandRequest) -> Result<(String, String), Box<dyn Error>> {
+        info!(
+            "[######][执行命令][@][tcs_run_command()][tcs_region: {}][instance_ids: {:?}][command_type: {}]",
+            tcs_region, request.instance_ids, request.command_type
+        );
+
+        let payload = serde_json::to_value(request)?;
+        let tcs_response_data: TcsResponseRunCommand = self.tcs_request_service_action(&TCS_SERVICE_TAT, "RunCommand", tcs_region, &payload)?;
+        Ok((tcs_response_data.command_id, tcs_response_data.invocation_id))
+    }
+
+    // 触发已保存的命令, parameters 为自定义参数(JSON 对象)
+    pub fn tcs_invoke_command(&mut self, tcs_region: &str, command_id: &str, instance_ids: &[String], parameters: Option<&serde_json::Value>) -> Result<String, Box<dyn Error>> {
+        info!("[######][触发命令][@][tcs_invoke_command()][tcs_region: {}][command_id: {}][instance_ids: {:?}]", tcs_region, command_id, instance_ids);
+
+        let mut payload = json!({
+            "CommandId": command_id,
+            "InstanceIds": instance_ids
+        });
+        if let Some(parameters) = parameters {
+            payload["Parameters"] = json!(parameters.to_string());
+        }
+
+        let tcs_response_data: TcsResponseInvokeCommand = self.tcs_request_service_action(&TCS_SERVICE_TAT, "InvokeCommand", tcs_region, &payload)?;
+        Ok(tcs_response_data.invocation_id)
+    }
+
+    // 查询执行活动
+    pub fn tcs_describe_invocations(&mut self, tcs_region: &str, invocation_ids: &[String]) -> Result<Vec<TcsInvocation>, Box<dyn Error>> {
+        info!("[######][查询执行活动][@][tcs_describe_invocations()][tcs_region: {}][invocation_ids: {:?}]", tcs_region, invocation_ids);
+
+        let payload = json!({
+            "InvocationIds": invocation_ids,
+            "Limit": 100
+        });
+
+        let tcs_response_data: TcsResponseDescribeInvocations = self.tcs_request_service_action(&TCS_SERVICE_TAT, "DescribeInvocations", tcs_region, &payload)?;
+        Ok(tcs_response_data.invocation_set)
+    }
+
+    // 查询执行活动下的执行任务(含输出)
+    pub fn tcs_describe_invocation_tasks(&mut self, tcs_region: &str, invocation_id: &str) -> Result<Vec<TcsInvocationTask>, Box<dyn Error>> {
+        info!("[######][查询执行任务][@][tcs_describe_invocation_tasks()][tcs_region: {}][invocation_id: {}]", tcs_region, invocation_id);
+
+        let limit = 100;
+        let filters = [TcsFilter::new("invocation-id", &[invocation_id])];
+        let mut invocation_task_set: Vec<TcsInvocationTask> = vec![];
+        loop {
+            let payload = json!({
+                "Filters": filters,
+                "HideOutput": false,
+                "Offset": invocation_task_set.len(),
+                "Limit": limit
+            });
+
+            let tcs_response_data: TcsResponseDescribeInvocationTasks = self.tcs_request_service_action(&TCS_SERVICE_TAT, "DescribeInvocationTasks", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.invocation_task_set.len();
+            invocation_task_set.extend(tcs_response_data.invocation_task_set);
+
+            if page_count == 0 || invocation_task_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(invocation_task_set)
+    }
+
+    // 等待执行活动结束, 返回各实例的执行任务
+    pub fn tcs_wait_invocation(&mut self, tcs_region: &str, invocation_id: &str, waiter: &TcsWaiter) -> Result<Vec<TcsInvocationTask>, Box<dyn Error>> {
+        let invocation_ids = [invocation_id.to_string()];
+        let invocation = waiter.wait(invocation_id, || {
+            let invocation_set = self.tcs_describe_invocations(tcs_region, &invocation_ids)?;
+            Ok(invocation_set.into_iter().find(|invocation| invocation.invocation_id == invocation_id && invocation.is_finished()))
+        })?;
+        info!("[执行活动结束][invocation_id: {}][invocation_status: {}]", invocation_id, invocation.invocation_status);
+
+        self.tcs_describe_invocation_tasks(tcs_region, invocation_id)
+    }
+
+    // 在实例上执行 Shell 脚本并等待结束(可在 tcs_wait_instance_state 之后调用)
+    pub fn tcs_run_and_wait(&mut self, tcs_region: &str, instance_ids: &[String], script: &str, waiter: &TcsWaiter) -> Result<Vec<TcsInvocationTask>, Box<dyn Error>> {
+        let mut request = TcsRunCommandRequest::shell(instance_ids, script);
+        // 命令超时不超过等待时间
+        request.timeout = waiter.timeout.as_secs().clamp(1, 86400);
+
+        let (_, invocation_id) = self.tcs_run_command(tcs_region, &request)?;
+        let invocation_task_set = self.tcs_wait_invocation(tcs_region, invocation_id.as_str(), waiter)?;
+        for task in &invocation_task_set {
+            info!("[执行结果][instance_id: {}][task_status: {}][exit_code: {:?}]", task.instance_id, task.task_status, task.exit_code());
+        }
+
+        Ok(invocation_task_set)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::fixture;
+
+    #[test]
+    fn decode_task_output() {
+        let request = TcsRunCommandRequest::shell(&["ins-1".to_string()], "echo hello");
+        assert_eq!(request.content, "ZWNobyBoZWxsbw==");
+
+        let task: TcsInvocationTask = fixture(json!({
+            "InvocationId": "inv-1",
+            "InvocationTaskId": "invt-1",
+            "CommandId": "cmd-1",
+            "InstanceId": "ins-1",
+            "TaskStatus": "SUCCESS",
+            "TaskResult": { "ExitCode": 0, "Output": "aGVsbG8K", "Dropped": 0 }
+        }));
+        assert!(task.is_success());
+        assert_eq!(task.output().unwrap(), "hello\n");
+    }
+}
@@ -0,0 +1,50 @@
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::{TcsData, TcsInfo, TcsInstanceInfo};
+
+// 由接口响应数据构造测试数据
+pub(crate) fn fixture<T: DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).unwrap()
+}
+
+// 实例信息, fields 覆盖默认字段
+pub(crate) fn instance_info(fields: Value) -> TcsInstanceInfo {
+    let mut value = json!({
+        "InstanceId": "ins-1",
+        "InstanceName": "spot-ins-1",
+        "InstanceType": "S5.MEDIUM4",
+        "InstanceChargeType": "SPOTPAID",
+        "InstanceState": "RUNNING",
+        "CPU": 2,
+        "Memory": 4,
+        "OsName": "CentOS",
+        "RestrictState": "NORMAL",
+        "SystemDisk": { "DiskType": "CLOUD_PREMIUM", "DiskSize": 50 }
+    });
+    if let (Some(value), Value::Object(fields)) = (value.as_object_mut(), fields) {
+        value.extend(fields);
+    }
+    fixture(value)
+}
+
+// 实例配置
+pub(crate) fn tcs_data(tcs_title: &str, instance_name: &str, instance_charge_type: &str) -> TcsData {
+    TcsData {
+        tcs_title: tcs_title.to_string(),
+        tcs_region: "ap-guangzhou".to_string(),
+        tcs_zone: "ap-guangzhou-3".to_string(),
+        host_name: instance_name.to_string(),
+        instance_name: instance_name.to_string(),
+        instance_id: format!("ins-{}", instance_name),
+        tcs_image_id: "img-new".to_string(),
+        password: "".to_string(),
+        key_ids: vec!["skey-1".to_string()],
+        tcs_info: TcsInfo {
+            instance_charge_type: instance_charge_type.to_string(),
+            instance_cpu: 2,
+            instance_memory: 4,
+            max_unit_price: 0.5,
+        },
+    }
+}
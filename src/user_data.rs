@@ -0,0 +1,186 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+use std::error::Error;
+
+// UserData 经 Base64 编码后的长度上限
+pub const TCS_USER_DATA_MAX_SIZE: usize = 16 * 1024;
+
+// 用户数据 Base64 编码, 并检查长度
+pub fn encode_user_data(user_data: &[u8]) -> Result<String, Box<dyn Error>> {
+    let encoded = STANDARD.encode(user_data);
+    if encoded.len() > TCS_USER_DATA_MAX_SIZE {
+        return Result::Err(format!("用户数据超出长度限制({} > {})!", encoded.len(), TCS_USER_DATA_MAX_SIZE).into());
+    }
+
+    Ok(encoded)
+}
+
+// cloud-init 配置
+// [cloud-config 示例](https://cloudinit.readthedocs.io/en/latest/reference/examples.html)
+#[derive(Debug, Clone, Default)]
+pub struct CloudConfig {
+    pub packages: Vec<String>,
+    pub write_files: Vec<CloudConfigFile>,
+    pub users: Vec<CloudConfigUser>,
+    pub runcmd: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CloudConfigFile {
+    pub path: String,
+    pub content: String,
+    // 例如 "0644"
+    pub permissions: Option<String>,
+    // 例如 "root:root"
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CloudConfigUser {
+    pub name: String,
+    pub groups: Vec<String>,
+    // 例如 "ALL=(ALL) NOPASSWD:ALL"
+    pub sudo: Option<String>,
+    pub shell: Option<String>,
+    pub ssh_authorized_keys: Vec<String>,
+}
+
+impl CloudConfig {
+    pub fn new() -> Self {
+        CloudConfig::default()
+    }
+
+    pub fn package(mut self, package: &str) -> Self {
+        self.packages.push(package.to_string());
+        self
+    }
+
+    pub fn write_file(mut self, file: CloudConfigFile) -> Self {
+        self.write_files.push(file);
+        self
+    }
+
+    pub fn user(mut self, user: CloudConfigUser) -> Self {
+        self.users.push(user);
+        self
+    }
+
+    pub fn runcmd(mut self, command: &str) -> Self {
+        self.runcmd.push(command.to_string());
+        self
+    }
+
+    // 生成 #cloud-config 文本(字符串统一使用 JSON 转义, 也是合法的 YAML 双引号字符串)
+    pub fn render(&self) -> String {
+        let mut lines = vec!["#cloud-config".to_string()];
+
+        if !self.packages.is_empty() {
+            lines.push("packages:".to_string());
+            lines.extend(self.packages.iter().map(|package| format!("  - {}", quote(package))));
+        }
+
+        if !self.write_files.is_empty() {
+            lines.push("write_files:".to_string());
+            for file in &self.write_files {
+                lines.push(format!("  - path: {}", quote(&file.path)));
+                lines.push(format!("    content: {}", quote(&file.content)));
+                if let Some(permissions) = &file.permissions {
+                    lines.push(format!("    permissions: {}", quote(permissions)));
+                }
+                if let Some(owner) = &file.owner {
+                    lines.push(format!("    owner: {}", quote(owner)));
+                }
+            }
+        }
+
+        if !self.users.is_empty() {
+            // 保留镜像默认用户
+            lines.push("users:".to_string());
+            lines.push("  - default".to_string());
+            for user in &self.users {
+                lines.push(format!("  - name: {}", quote(&user.name)));
+                if !user.groups.is_empty() {
+                    lines.push(format!("    groups: {}", quote(&user.groups.join(", "))));
+                }
+                if let Some(sudo) = &user.sudo {
+                    lines.push(format!("    sudo: {}", quote(sudo)));
+                }
+                if let Some(shell) = &user.shell {
+                    lines.push(format!("    shell: {}", quote(shell)));
+                }
+                if !user.ssh_authorized_keys.is_empty() {
+                    lines.push("    ssh_authorized_keys:".to_string());
+                    lines.extend(user.ssh_authorized_keys.iter().map(|key| format!("      - {}", quote(key))));
+                }
+            }
+        }
+
+        if !self.runcmd.is_empty() {
+            lines.push("runcmd:".to_string());
+            lines.extend(self.runcmd.iter().map(|command| format!("  - {}", quote(command))));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    // 生成可直接用于 UserData 的 Base64 数据
+    pub fn to_user_data(&self) -> Result<String, Box<dyn Error>> {
+        encode_user_data(self.render().as_bytes())
+    }
+}
+
+fn quote(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn user_data_size_limit() {
+        assert_eq!(encode_user_data(b"#!/bin/sh\necho ok\n").unwrap(), "IyEvYmluL3NoCmVjaG8gb2sK");
+
+        // 12 KB 原始数据编码后正好 16 KB
+        assert!(encode_user_data(&[0u8; 12 * 1024]).is_ok());
+        assert!(encode_user_data(&[0u8; 12 * 1024 + 1]).is_err());
+    }
+
+    #[test]
+    fn render_cloud_config() {
+        let cloud_config = CloudConfig::new()
+            .package("nginx")
+            .write_file(CloudConfigFile {
+                path: "/etc/motd".to_string(),
+                content: "hello \"tcs\"\n".to_string(),
+                permissions: Some("0644".to_string()),
+                ..Default::default()
+            })
+            .user(CloudConfigUser {
+                name: "deploy".to_string(),
+                groups: vec!["wheel".to_string(), "docker".to_string()],
+                ssh_authorized_keys: vec!["ssh-ed25519 AAAA deploy".to_string()],
+                ..Default::default()
+            })
+            .runcmd("systemctl enable --now nginx");
+
+        let expected = r#"#cloud-config
+packages:
+  - "nginx"
+write_files:
+  - path: "/etc/motd"
+    content: "hello \"tcs\"\n"
+    permissions: "0644"
+users:
+  - default
+  - name: "deploy"
+    groups: "wheel, docker"
+    ssh_authorized_keys:
+      - "ssh-ed25519 AAAA deploy"
+runcmd:
+  - "systemctl enable --now nginx"
+"#;
+        assert_eq!(cloud_config.render(), expected);
+    }
+}
@@ -0,0 +1,401 @@
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use std::error::Error;
+
+use crate::{TCS_SERVICE_VPC, TcsFilter, TcsResponseEmpty, TcsTag, TencentCloudApi};
+
+// 私有网络
+// [查询VPC列表](https://cloud.tencent.com/document/api/215/15778)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsVpc {
+    #[serde(rename = "VpcId")]
+    pub vpc_id: String,
+    #[serde(rename = "VpcName")]
+    pub vpc_name: String,
+    #[serde(rename = "CidrBlock")]
+    pub cidr_block: String,
+    #[serde(rename = "IsDefault")]
+    pub is_default: bool,
+    #[serde(rename = "EnableMulticast")]
+    pub enable_multicast: Option<bool>,
+    #[serde(rename = "DnsServerSet")]
+    pub dns_server_set: Option<Vec<String>>,
+    #[serde(rename = "DomainName")]
+    pub domain_name: Option<String>,
+    #[serde(rename = "CreatedTime")]
+    pub created_time: Option<String>,
+    #[serde(rename = "TagSet", default, deserialize_with = "crate::null_default")]
+    pub tag_set: Vec<TcsTag>,
+}
+
+// 子网
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsSubnet {
+    #[serde(rename = "VpcId")]
+    pub vpc_id: String,
+    #[serde(rename = "SubnetId")]
+    pub subnet_id: String,
+    #[serde(rename = "SubnetName")]
+    pub subnet_name: String,
+    #[serde(rename = "CidrBlock")]
+    pub cidr_block: String,
+    #[serde(rename = "Zone")]
+    pub zone: String,
+    #[serde(rename = "IsDefault")]
+    pub is_default: bool,
+    #[serde(rename = "RouteTableId")]
+    pub route_table_id: Option<String>,
+    #[serde(rename = "AvailableIpAddressCount")]
+    pub available_ip_address_count: Option<u64>,
+    #[serde(rename = "CreatedTime")]
+    pub created_time: Option<String>,
+    #[serde(rename = "TagSet", default, deserialize_with = "crate::null_default")]
+    pub tag_set: Vec<TcsTag>,
+}
+
+// 路由表
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsRouteTable {
+    #[serde(rename = "VpcId")]
+    pub vpc_id: String,
+    #[serde(rename = "RouteTableId")]
+    pub route_table_id: String,
+    #[serde(rename = "RouteTableName")]
+    pub route_table_name: String,
+    #[serde(rename = "Main")]
+    pub main: bool,
+    #[serde(rename = "AssociationSet", default, deserialize_with = "crate::null_default")]
+    pub association_set: Vec<TcsRouteTableAssociation>,
+    #[serde(rename = "RouteSet", default, deserialize_with = "crate::null_default")]
+    pub route_set: Vec<TcsRoute>,
+    #[serde(rename = "CreatedTime")]
+    pub created_time: Option<String>,
+    #[serde(rename = "TagSet", default, deserialize_with = "crate::null_default")]
+    pub tag_set: Vec<TcsTag>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TcsRouteTableAssociation {
+    #[serde(rename = "SubnetId")]
+    pub subnet_id: String,
+    #[serde(rename = "RouteTableId")]
+    pub route_table_id: String,
+}
+
+// 路由策略(创建时只需填写目的网段/下一跳类型/下一跳)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TcsRoute {
+    #[serde(rename = "DestinationCidrBlock")]
+    pub destination_cidr_block: String,
+    // CVM / VPN / DIRECTCONNECT / PEERCONNECTION / HAVIP / NAT / EIP / LOCAL_GATEWAY ...
+    #[serde(rename = "GatewayType")]
+    pub gateway_type: String,
+    #[serde(rename = "GatewayId")]
+    pub gateway_id: String,
+    #[serde(rename = "RouteDescription", skip_serializing_if = "Option::is_none")]
+    pub route_description: Option<String>,
+    #[serde(rename = "RouteId", skip_serializing_if = "Option::is_none")]
+    pub route_id: Option<u64>,
+    #[serde(rename = "RouteItemId", skip_serializing_if = "Option::is_none")]
+    pub route_item_id: Option<String>,
+    #[serde(rename = "Enabled", skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(rename = "RouteType", skip_serializing_if = "Option::is_none")]
+    pub route_type: Option<String>,
+}
+
+impl TcsRoute {
+    pub fn new(destination_cidr_block: &str, gateway_type: &str, gateway_id: &str) -> Self {
+        TcsRoute {
+            destination_cidr_block: destination_cidr_block.to_string(),
+            gateway_type: gateway_type.to_string(),
+            gateway_id: gateway_id.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeVpcs {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "VpcSet")]
+    pub vpc_set: Vec<TcsVpc>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateVpc {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "Vpc")]
+    pub vpc: TcsVpc,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeSubnets {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "SubnetSet")]
+    pub subnet_set: Vec<TcsSubnet>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateSubnet {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "Subnet")]
+    pub subnet: TcsSubnet,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseDescribeRouteTables {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "TotalCount")]
+    pub total_count: u32,
+    #[serde(rename = "RouteTableSet")]
+    pub route_table_set: Vec<TcsRouteTable>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateRouteTable {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "RouteTable")]
+    pub route_table: TcsRouteTable,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcsResponseCreateRoutes {
+    #[serde(rename = "RequestId")]
+    pub request_id: String,
+    #[serde(rename = "RouteTableSet")]
+    pub route_table_set: Vec<TcsRouteTable>,
+}
+
+// 私有网络接口的 Offset/Limit 为字符串类型
+fn tcs_vpc_page_payload(id_key: &str, ids: &[String], filters: &[TcsFilter], offset: usize, limit: usize) -> Value {
+    let mut payload = json!({
+        "Offset": offset.to_string(),
+        "Limit": limit.to_string()
+    });
+    // ID 列表与 Filters 不能同时指定
+    if !ids.is_empty() {
+        payload[id_key] = json!(ids);
+    } else if !filters.is_empty() {
+        payload["Filters"] = json!(filters);
+    }
+    payload
+}
+
+impl TencentCloudApi {
+    // 查询私有网络列表
+    pub fn tcs_describe_vpcs(&mut self, tcs_region: &str, vpc_ids: &[String], filters: &[TcsFilter]) -> Result<Vec<TcsVpc>, Box<dyn Error>> {
+        info!("[######][查询私有网络列表][@][tcs_describe_vpcs()][tcs_region: {}][vpc_ids: {:?}][filters: {:?}]", tcs_region, vpc_ids, filters);
+
+        let limit = 100;
+        let mut vpc_set: Vec<TcsVpc> = vec![];
+        loop {
+            let payload = tcs_vpc_page_payload("VpcIds", vpc_ids, filters, vpc_set.len(), limit);
+            let tcs_response_data: TcsResponseDescribeVpcs = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DescribeVpcs", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.vpc_set.len();
+            vpc_set.extend(tcs_response_data.vpc_set);
+
+            if page_count == 0 || vpc_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(vpc_set)
+    }
+
+    // 创建私有网络
+    pub fn tcs_create_vpc(&mut self, tcs_region: &str, vpc_name: &str, cidr_block: &str, tags: &[TcsTag]) -> Result<TcsVpc, Box<dyn Error>> {
+        info!("[######][创建私有网络][@][tcs_create_vpc()][tcs_region: {}][vpc_name: {}][cidr_block: {}]", tcs_region, vpc_name, cidr_block);
+
+        let payload = json!({
+            "VpcName": vpc_name,
+            "CidrBlock": cidr_block,
+            "Tags": tags
+        });
+
+        let tcs_response_data: TcsResponseCreateVpc = self.tcs_request_service_action(&TCS_SERVICE_VPC, "CreateVpc", tcs_region, &payload)?;
+        Ok(tcs_response_data.vpc)
+    }
+
+    // 删除私有网络(需先删除子网等资源)
+    pub fn tcs_delete_vpc(&mut self, tcs_region: &str, vpc_id: &str) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除私有网络][@][tcs_delete_vpc()][tcs_region: {}][vpc_id: {}]", tcs_region, vpc_id);
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DeleteVpc", tcs_region, &json!({ "VpcId": vpc_id }))?;
+        Ok(())
+    }
+
+    // 查询子网列表
+    pub fn tcs_describe_subnets(&mut self, tcs_region: &str, subnet_ids: &[String], filters: &[TcsFilter]) -> Result<Vec<TcsSubnet>, Box<dyn Error>> {
+        info!("[######][查询子网列表][@][tcs_describe_subnets()][tcs_region: {}][subnet_ids: {:?}][filters: {:?}]", tcs_region, subnet_ids, filters);
+
+        let limit = 100;
+        let mut subnet_set: Vec<TcsSubnet> = vec![];
+        loop {
+            let payload = tcs_vpc_page_payload("SubnetIds", subnet_ids, filters, subnet_set.len(), limit);
+            let tcs_response_data: TcsResponseDescribeSubnets = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DescribeSubnets", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.subnet_set.len();
+            subnet_set.extend(tcs_response_data.subnet_set);
+
+            if page_count == 0 || subnet_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(subnet_set)
+    }
+
+    // 创建子网
+    pub fn tcs_create_subnet(&mut self, tcs_region: &str, vpc_id: &str, subnet_name: &str, cidr_block: &str, zone: &str, tags: &[TcsTag]) -> Result<TcsSubnet, Box<dyn Error>> {
+        info!(
+            "[######][创建子网][@][tcs_create_subnet()][tcs_region: {}][vpc_id: {}][subnet_name: {}][cidr_block: {}][zone: {}]",
+            tcs_region, vpc_id, subnet_name, cidr_block, zone
+        );
+
+        let payload = json!({
+            "VpcId": vpc_id,
+            "SubnetName": subnet_name,
+            "CidrBlock": cidr_block,
+            "Zone": zone,
+            "Tags": tags
+        });
+
+        let tcs_response_data: TcsResponseCreateSubnet = self.tcs_request_service_action(&TCS_SERVICE_VPC, "CreateSubnet", tcs_region, &payload)?;
+        Ok(tcs_response_data.subnet)
+    }
+
+    // 删除子网(子网内不能有实例)
+    pub fn tcs_delete_subnet(&mut self, tcs_region: &str, subnet_id: &str) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除子网][@][tcs_delete_subnet()][tcs_region: {}][subnet_id: {}]", tcs_region, subnet_id);
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DeleteSubnet", tcs_region, &json!({ "SubnetId": subnet_id }))?;
+        Ok(())
+    }
+
+    // 查询路由表列表
+    pub fn tcs_describe_route_tables(&mut self, tcs_region: &str, route_table_ids: &[String], filters: &[TcsFilter]) -> Result<Vec<TcsRouteTable>, Box<dyn Error>> {
+        info!(
+            "[######][查询路由表列表][@][tcs_describe_route_tables()][tcs_region: {}][route_table_ids: {:?}][filters: {:?}]",
+            tcs_region, route_table_ids, filters
+        );
+
+        let limit = 100;
+        let mut route_table_set: Vec<TcsRouteTable> = vec![];
+        loop {
+            let payload = tcs_vpc_page_payload("RouteTableIds", route_table_ids, filters, route_table_set.len(), limit);
+            let tcs_response_data: TcsResponseDescribeRouteTables = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DescribeRouteTables", tcs_region, &payload)?;
+
+            let total_count = tcs_response_data.total_count as usize;
+            let page_count = tcs_response_data.route_table_set.len();
+            route_table_set.extend(tcs_response_data.route_table_set);
+
+            if page_count == 0 || route_table_set.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(route_table_set)
+    }
+
+    // 创建路由表
+    pub fn tcs_create_route_table(&mut self, tcs_region: &str, vpc_id: &str, route_table_name: &str, tags: &[TcsTag]) -> Result<TcsRouteTable, Box<dyn Error>> {
+        info!("[######][创建路由表][@][tcs_create_route_table()][tcs_region: {}][vpc_id: {}][route_table_name: {}]", tcs_region, vpc_id, route_table_name);
+
+        let payload = json!({
+            "VpcId": vpc_id,
+            "RouteTableName": route_table_name,
+            "Tags": tags
+        });
+
+        let tcs_response_data: TcsResponseCreateRouteTable = self.tcs_request_service_action(&TCS_SERVICE_VPC, "CreateRouteTable", tcs_region, &payload)?;
+        Ok(tcs_response_data.route_table)
+    }
+
+    // 删除路由表(默认路由表及已关联子网的路由表不能删除)
+    pub fn tcs_delete_route_table(&mut self, tcs_region: &str, route_table_id: &str) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除路由表][@][tcs_delete_route_table()][tcs_region: {}][route_table_id: {}]", tcs_region, route_table_id);
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DeleteRouteTable", tcs_region, &json!({ "RouteTableId": route_table_id }))?;
+        Ok(())
+    }
+
+    // 修改子网关联的路由表
+    pub fn tcs_replace_route_table_association(&mut self, tcs_region: &str, subnet_id: &str, route_table_id: &str) -> Result<(), Box<dyn Error>> {
+        info!(
+            "[######][修改子网关联路由表][@][tcs_replace_route_table_association()][tcs_region: {}][subnet_id: {}][route_table_id: {}]",
+            tcs_region, subnet_id, route_table_id
+        );
+
+        let payload = json!({
+            "SubnetId": subnet_id,
+            "RouteTableId": route_table_id
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_VPC, "ReplaceRouteTableAssociation", tcs_region, &payload)?;
+        Ok(())
+    }
+
+    // 新增路由策略, 返回更新后的路由表
+    pub fn tcs_create_routes(&mut self, tcs_region: &str, route_table_id: &str, routes: &[TcsRoute]) -> Result<TcsRouteTable, Box<dyn Error>> {
+        info!("[######][新增路由策略][@][tcs_create_routes()][tcs_region: {}][route_table_id: {}][routes: {:?}]", tcs_region, route_table_id, routes);
+
+        let payload = json!({
+            "RouteTableId": route_table_id,
+            "Routes": routes
+        });
+
+        let tcs_response_data: TcsResponseCreateRoutes = self.tcs_request_service_action(&TCS_SERVICE_VPC, "CreateRoutes", tcs_region, &payload)?;
+        tcs_response_data.route_table_set.into_iter().next().ok_or_else(|| "请求成功,但未返回路由表!".into())
+    }
+
+    // 删除路由策略(按路由策略 ID)
+    pub fn tcs_delete_routes(&mut self, tcs_region: &str, route_table_id: &str, route_ids: &[u64]) -> Result<(), Box<dyn Error>> {
+        info!("[######][删除路由策略][@][tcs_delete_routes()][tcs_region: {}][route_table_id: {}][route_ids: {:?}]", tcs_region, route_table_id, route_ids);
+
+        let routes: Vec<Value> = route_ids.iter().map(|route_id| json!({ "RouteId": route_id })).collect();
+        let payload = json!({
+            "RouteTableId": route_table_id,
+            "Routes": routes
+        });
+
+        let _: TcsResponseEmpty = self.tcs_request_service_action(&TCS_SERVICE_VPC, "DeleteRoutes", tcs_region, &payload)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn vpc_page_payload() {
+        let payload = tcs_vpc_page_payload("SubnetIds", &[], &[TcsFilter::new("vpc-id", &["vpc-1"])], 100, 100);
+        assert_eq!(
+            payload,
+            json!({
+                "Offset": "100",
+                "Limit": "100",
+                "Filters": [{ "Name": "vpc-id", "Values": ["vpc-1"] }]
+            })
+        );
+
+        let route = serde_json::to_value(TcsRoute::new("10.0.0.0/8", "VPN", "vpngw-1")).unwrap();
+        assert_eq!(route, json!({ "DestinationCidrBlock": "10.0.0.0/8", "GatewayType": "VPN", "GatewayId": "vpngw-1" }));
+    }
+}
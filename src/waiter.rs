@@ -0,0 +1,72 @@
+use log::*;
+
+use std::error::Error;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// 轮询等待配置
+#[derive(Debug, Clone)]
+pub struct TcsWaiter {
+    pub timeout: Duration,
+    pub interval: Duration,
+}
+
+impl Default for TcsWaiter {
+    fn default() -> Self {
+        TcsWaiter {
+            timeout: Duration::from_secs(600),
+            interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl TcsWaiter {
+    pub fn new(timeout: Duration, interval: Duration) -> Self {
+        TcsWaiter { timeout, interval }
+    }
+
+    // 轮询直到返回 Some(..) 或超时, 返回 Err(..) 时立即结束
+    pub fn wait<T, F>(&self, tcs_target: &str, mut poll: F) -> Result<T, Box<dyn Error>>
+    where
+        F: FnMut() -> Result<Option<T>, Box<dyn Error>>,
+    {
+        let started = Instant::now();
+
+        loop {
+            if let Some(value) = poll()? {
+                info!("[等待完成][tcs_target: {}][elapsed: {:?}]", tcs_target, started.elapsed());
+                return Ok(value);
+            }
+
+            if started.elapsed() + self.interval > self.timeout {
+                warn!("[等待超时][tcs_target: {}][timeout: {:?}]", tcs_target, self.timeout);
+                return Err(format!("等待超时({})!", tcs_target).into());
+            }
+
+            debug!("[等待中][tcs_target: {}][elapsed: {:?}]", tcs_target, started.elapsed());
+            thread::sleep(self.interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wait_until_ready_or_timeout() {
+        let waiter = TcsWaiter::new(Duration::from_millis(50), Duration::from_millis(1));
+
+        let mut polls = 0;
+        let value = waiter
+            .wait("ready", || {
+                polls += 1;
+                Ok(if polls == 3 { Some(polls) } else { None })
+            })
+            .unwrap();
+        assert_eq!(value, 3);
+
+        let result: Result<(), _> = waiter.wait("never", || Ok(None));
+        assert!(result.is_err());
+    }
+}